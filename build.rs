@@ -0,0 +1,50 @@
+//! Finds every `src/dayNN.rs` whose `dayNN` feature is on and writes the
+//! `days!` invocation that registers them, so adding a day is its file, a
+//! `mod` line in `lib.rs` and a feature in `Cargo.toml`. A day missing its
+//! feature fails the build rather than quietly going unregistered, and one
+//! missing its `mod` line fails where the registry names it.
+
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo::rerun-if-changed=src");
+
+    let all_days = env::var_os("CARGO_FEATURE_ALL_DAYS").is_some();
+    let mut days: Vec<(u8, String)> = fs::read_dir("src")
+        .expect("src dir")
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let num = name.strip_prefix("day")?.strip_suffix(".rs")?;
+            if num.len() != 2 || !num.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            if env::var_os(format!("CARGO_FEATURE_DAY{num}")).is_none() {
+                // `all-days` turns on every day feature there is, so this
+                // file's is missing from it or from Cargo.toml altogether
+                assert!(
+                    !all_days,
+                    "src/{name} has no feature: add `day{num} = []` to [features] \
+                     in Cargo.toml and list it in `all-days`"
+                );
+                return None;
+            }
+            Some((num.parse().ok()?, format!("day{num}")))
+        })
+        .collect();
+    days.sort();
+
+    // `registry::solver` has nothing to build without a day
+    println!("cargo::rustc-check-cfg=cfg(any_day)");
+    if !days.is_empty() {
+        println!("cargo::rustc-cfg=any_day");
+    }
+
+    let mut out = String::from("days! { $\n");
+    for (day, module) in days {
+        out += &format!("    {day} => {module}::Day{day};\n");
+    }
+    out += "}\n";
+
+    let dest = Path::new(&env::var("OUT_DIR").unwrap()).join("days.rs");
+    fs::write(dest, out).expect("writing days.rs");
+}
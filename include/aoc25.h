@@ -0,0 +1,74 @@
+/* Generated by src/bin/gen_header.rs from src/ffi.rs; don't edit. */
+
+#ifndef AOC25_H
+#define AOC25_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+/*
+ * Success.
+ */
+#define AOC_OK 0
+
+/*
+ * No solver is registered for the day.
+ */
+#define AOC_NO_DAY 1
+
+/*
+ * The part isn't 1 or 2, or the day doesn't really implement it.
+ */
+#define AOC_NO_PART 2
+
+/*
+ * The input isn't UTF-8.
+ */
+#define AOC_BAD_UTF8 3
+
+/*
+ * The solver panicked, usually on input it couldn't parse.
+ */
+#define AOC_PANICKED 4
+
+/*
+ * A required pointer was null.
+ */
+#define AOC_NULL 5
+
+/*
+ * Writes up to cap registered day numbers to out and returns how many
+ * there are in all, so a first call with cap 0 sizes the buffer.
+ *
+ * # Safety
+ *
+ * out must be valid for cap writes, or may be null if cap is 0.
+ */
+size_t aoc_days(uint8_t *out, size_t cap);
+
+/*
+ * Parts day really implements, or 0 if there's no solver for it.
+ */
+uint8_t aoc_parts(uint8_t day);
+
+/*
+ * Solves part of day for the len bytes of UTF-8 at input. On
+ * AOC_OK, *answer is a NUL-terminated string to release with
+ * aoc_free; on any other code it's set to null.
+ *
+ * # Safety
+ *
+ * input must be valid for len reads and answer valid for one write.
+ */
+int aoc_run(uint8_t day, uint8_t part, const uint8_t *input, size_t len, char **answer);
+
+/*
+ * Releases an answer from aoc_run. Null is ignored.
+ *
+ * # Safety
+ *
+ * answer must come from aoc_run and not have been freed already.
+ */
+void aoc_free(char *answer);
+
+#endif /* AOC25_H */
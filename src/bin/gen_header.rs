@@ -0,0 +1,117 @@
+//! Writes `include/aoc25.h` from the `pub` consts and `extern "C"` fns in
+//! `src/ffi.rs`, keeping their doc comments:
+//!
+//! ```text
+//! cargo run --bin gen_header
+//! ```
+//!
+//! The header is checked in, and a test here fails once it's out of date.
+
+use std::{fs, path::Path};
+
+const FFI: &str = include_str!("../ffi.rs");
+const DEST: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/include/aoc25.h");
+
+fn main() {
+    let dest = Path::new(DEST);
+    fs::create_dir_all(dest.parent().unwrap()).expect("include dir");
+    fs::write(dest, header(FFI)).expect("writing include/aoc25.h");
+}
+
+/// The C header for `src`, the text of `ffi.rs`.
+fn header(src: &str) -> String {
+    let mut out = String::from(
+        "/* Generated by src/bin/gen_header.rs from src/ffi.rs; don't edit. */\n\n\
+         #ifndef AOC25_H\n#define AOC25_H\n\n#include <stddef.h>\n#include <stdint.h>\n\n",
+    );
+
+    let mut docs = vec![];
+    let mut lines = src.lines().map(str::trim);
+    while let Some(line) = lines.next() {
+        if let Some(doc) = line.strip_prefix("///") {
+            docs.push(doc.strip_prefix(' ').unwrap_or(doc).replace('`', ""));
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("pub const ") {
+            // pub const NAME: c_int = 1;
+            let (name, value) = rest.split_once(':').expect("const type");
+            let value = value.split_once('=').expect("const value").1;
+            out += &comment(&docs);
+            out += &format!("#define {name} {}\n\n", value.trim().trim_end_matches(';'));
+        } else if line.starts_with("pub ") && line.contains("extern \"C\" fn ") {
+            let mut sig = line.to_string();
+            while !sig.ends_with('{') {
+                sig += lines.next().expect("fn body");
+            }
+            out += &comment(&docs);
+            out += &c_fn(&sig);
+        }
+
+        if !line.starts_with("#[") {
+            docs.clear();
+        }
+    }
+
+    out + "#endif /* AOC25_H */\n"
+}
+
+fn comment(docs: &[String]) -> String {
+    if docs.is_empty() {
+        return String::new();
+    }
+    let mut out = String::from("/*\n");
+    for d in docs {
+        out += &format!(" *{}{d}\n", if d.is_empty() { "" } else { " " });
+    }
+    out + " */\n"
+}
+
+/// `pub unsafe extern "C" fn name(a: T, ...) -> R {` as a C prototype.
+fn c_fn(sig: &str) -> String {
+    let (_, rest) = sig.split_once(" fn ").unwrap();
+    let (name, rest) = rest.split_once('(').unwrap();
+    let (args, ret) = rest.rsplit_once(')').unwrap();
+    let ret = ret.trim().trim_end_matches('{').trim();
+    let ret = ret.strip_prefix("->").map_or("void", |r| c_type(r.trim()));
+
+    let args: Vec<String> = args
+        .split(',')
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+        .map(|a| {
+            let (n, t) = a.split_once(':').expect("arg type");
+            let t = c_type(t.trim());
+            format!("{t}{}{}", if t.ends_with('*') { "" } else { " " }, n.trim())
+        })
+        .collect();
+
+    format!("{ret} {name}({});\n\n", args.join(", "))
+}
+
+fn c_type(t: &str) -> &'static str {
+    match t {
+        "u8" => "uint8_t",
+        "usize" => "size_t",
+        "c_int" => "int",
+        "*mut u8" => "uint8_t *",
+        "*const u8" => "const uint8_t *",
+        "*mut c_char" => "char *",
+        "*mut *mut c_char" => "char **",
+        _ => panic!("no C type for {t:?} in src/ffi.rs; add one to src/bin/gen_header.rs"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tup_to_date() {
+        let checked_in = fs::read_to_string(DEST).unwrap();
+        assert!(
+            checked_in == header(FFI),
+            "include/aoc25.h is stale; run `cargo run --bin gen_header`"
+        );
+    }
+}
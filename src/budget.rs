@@ -0,0 +1,89 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, AtomicU64, Ordering::Relaxed},
+};
+
+/// How far a part may go before giving up. Clones share state, so a runner
+/// can keep one to `cancel` from another thread, or to ask afterwards
+/// whether the answer it got is only a partial one.
+///
+/// Solvers call `step` in their loops and, once it says stop, return the
+/// best they have so far.
+#[derive(Debug, Clone, Default)]
+pub struct Budget(Arc<Inner>);
+
+#[derive(Debug, Default)]
+struct Inner {
+    limit: Option<u64>,
+    used: AtomicU64,
+    cancelled: AtomicBool,
+    stopped: AtomicBool,
+}
+
+impl Budget {
+    /// No step limit; only `cancel` stops it.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// At most `limit` steps, whatever a step is for the solver at hand.
+    pub fn steps(limit: u64) -> Self {
+        Budget(Arc::new(Inner {
+            limit: Some(limit),
+            ..Inner::default()
+        }))
+    }
+
+    /// Asks every solver using this budget to stop at its next `step`.
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Relaxed);
+    }
+
+    /// Counts a step. `false` means stop now; the solver's answer then
+    /// counts as partial.
+    pub fn step(&self) -> bool {
+        let used = self.0.used.fetch_add(1, Relaxed) + 1;
+        let over = self.0.limit.is_some_and(|l| used > l);
+
+        if over || self.0.cancelled.load(Relaxed) {
+            self.0.stopped.store(true, Relaxed);
+            return false;
+        }
+        true
+    }
+
+    pub fn used(&self) -> u64 {
+        self.0.used.load(Relaxed)
+    }
+
+    /// Did a solver stop early, leaving a best-so-far answer?
+    pub fn stopped(&self) -> bool {
+        self.0.stopped.load(Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tsteps() {
+        let b = Budget::steps(2);
+        assert!(b.step() && b.step());
+        assert!(!b.stopped());
+        assert!(!b.step());
+        assert!(b.stopped());
+        assert_eq!(b.used(), 3);
+    }
+
+    #[test]
+    fn tcancel() {
+        let b = Budget::new();
+        let runner = b.clone();
+        assert!(b.step());
+
+        std::thread::spawn(move || runner.cancel()).join().unwrap();
+        assert!(!b.step());
+        assert!(b.stopped());
+    }
+}
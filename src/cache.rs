@@ -0,0 +1,144 @@
+use std::{
+    env, fs,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+
+use crate::config::{cache_dir, config};
+
+/// Store new cache entries gzipped: `AOC_COMPRESS=1` or `compress = true` in the config.
+pub fn compress_enabled() -> bool {
+    match env::var("AOC_COMPRESS") {
+        Ok(v) => matches!(v.as_str(), "1" | "true" | "yes"),
+        Err(_) => config().compress,
+    }
+}
+
+fn gz_path(path: &Path) -> PathBuf {
+    let mut p = path.as_os_str().to_owned();
+    p.push(".gz");
+    p.into()
+}
+
+#[cfg(any(feature = "fetch", test))]
+pub(crate) fn is_cached(path: &Path) -> bool {
+    path.exists() || gz_path(path).exists()
+}
+
+/// Read `path`, or `path.gz` if only the compressed copy exists.
+pub(crate) fn read_cached(path: &Path) -> Option<String> {
+    if let Ok(s) = fs::read_to_string(path) {
+        return Some(s);
+    }
+
+    let f = fs::File::open(gz_path(path)).ok()?;
+    let mut s = String::new();
+    GzDecoder::new(f).read_to_string(&mut s).ok()?;
+
+    Some(s)
+}
+
+pub(crate) fn write_cached(path: &Path, contents: &str, compress: bool) -> std::io::Result<()> {
+    if compress {
+        write_atomic(&gz_path(path), &gzip(contents.as_bytes())?)
+    } else {
+        write_atomic(path, contents.as_bytes())
+    }
+}
+
+fn gzip(bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut enc = GzEncoder::new(Vec::new(), Compression::best());
+    enc.write_all(bytes)?;
+    enc.finish()
+}
+
+/// Write via a temp file and rename, so waiting readers never see a partial file.
+pub(crate) fn write_atomic(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let tmp = tmp_path(path);
+    fs::write(&tmp, contents)?;
+    fs::rename(&tmp, path)
+}
+
+/// `1.txt` -> `1.txt.tmp.<pid>`, keeping the whole name so `1.txt` and
+/// `1.html` don't share one.
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut p = path.as_os_str().to_owned();
+    p.push(format!(".tmp.{}", std::process::id()));
+    p.into()
+}
+
+/// Gzip every plain input and puzzle page in the cache, returning how many were converted.
+pub fn compress_cache() -> Result<usize, anyhow::Error> {
+    compress_dir(&cache_dir())
+}
+
+fn compress_dir(dir: &Path) -> Result<usize, anyhow::Error> {
+    let mut n = 0;
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let plain = path
+            .extension()
+            .is_some_and(|ext| ext == "txt" || ext == "html");
+        if !plain || !path.is_file() {
+            continue;
+        }
+
+        let contents = fs::read_to_string(&path)?;
+        write_cached(&path, &contents, true)?;
+        fs::remove_file(&path)?;
+        n += 1;
+    }
+
+    Ok(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scratch::Scratch;
+
+    #[test]
+    fn troundtrip() {
+        let dir = Scratch::new("cache-roundtrip");
+        let plain = dir.join("1.txt");
+        let packed = dir.join("2.txt");
+
+        write_cached(&plain, "L68\nL30\n", false).unwrap();
+        write_cached(&packed, "L68\nL30\n", true).unwrap();
+
+        assert!(plain.exists());
+        assert!(!packed.exists());
+        assert!(dir.join("2.txt.gz").exists());
+        assert!(is_cached(&packed));
+
+        assert_eq!(read_cached(&plain).unwrap(), "L68\nL30\n");
+        assert_eq!(read_cached(&packed).unwrap(), "L68\nL30\n");
+        assert_eq!(read_cached(&dir.join("3.txt")), None);
+    }
+
+    #[test]
+    fn ttmp_path() {
+        let txt = tmp_path(Path::new("inputs/1.txt"));
+        assert_ne!(txt, tmp_path(Path::new("inputs/1.html")));
+        assert!(
+            txt.to_string_lossy()
+                .ends_with(&format!("1.txt.tmp.{}", std::process::id()))
+        );
+    }
+
+    #[test]
+    fn tcompress_dir() {
+        let dir = Scratch::new("cache-compress");
+        write_cached(&dir.join("1.txt"), "one", false).unwrap();
+        write_cached(&dir.join("1.html"), "<p>one</p>", false).unwrap();
+        write_cached(&dir.join("2.txt"), "two", true).unwrap();
+
+        assert_eq!(compress_dir(&dir).unwrap(), 2);
+        assert!(!dir.join("1.txt").exists());
+        assert_eq!(read_cached(&dir.join("1.txt")).unwrap(), "one");
+        assert_eq!(read_cached(&dir.join("1.html")).unwrap(), "<p>one</p>");
+        assert_eq!(read_cached(&dir.join("2.txt")).unwrap(), "two");
+    }
+}
@@ -0,0 +1,120 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use regex::Regex;
+
+use crate::{
+    fetch::{DAYS, YEAR, base_url, get},
+    registry::solvers,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Progress {
+    /// Both stars on the site.
+    Solved,
+    /// Both parts implemented here, but not both stars submitted.
+    Unsubmitted,
+    /// Registered, but a part still returns a dummy answer.
+    Placeholder,
+    /// No solver at all.
+    Missing,
+}
+
+impl Display for Progress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Progress::Solved => "solved",
+            Progress::Unsubmitted => "implemented, not submitted",
+            Progress::Placeholder => "placeholder",
+            Progress::Missing => "missing",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Stars per day for the logged-in user, from the event's calendar page.
+pub fn fetch_stars() -> Result<BTreeMap<u8, u8>, anyhow::Error> {
+    Ok(parse_calendar(&get(&format!("{}/{YEAR}", base_url()))?))
+}
+
+/// Days without a `calendar-dayN` element (not yet unlocked) are left out.
+pub fn parse_calendar(page: &str) -> BTreeMap<u8, u8> {
+    let re = Regex::new(r#"class="calendar-day(\d+)([^"]*)""#).unwrap();
+    re.captures_iter(page)
+        .filter_map(|c| {
+            let day = c[1].parse().ok()?;
+            let stars = if c[2].contains("calendar-verycomplete") {
+                2
+            } else if c[2].contains("calendar-complete") {
+                1
+            } else {
+                0
+            };
+            Some((day, stars))
+        })
+        .collect()
+}
+
+/// Merge site stars with what's registered locally, one row per day.
+pub fn progress(stars: &BTreeMap<u8, u8>) -> Vec<(u8, u8, Progress)> {
+    let registered = solvers();
+    (1..=DAYS)
+        .map(|day| {
+            let stars = stars.get(&day).copied().unwrap_or(0);
+            let progress = match registered.iter().find(|s| s.day == day) {
+                None => Progress::Missing,
+                Some(s) if s.parts < 2 => Progress::Placeholder,
+                Some(_) if stars < 2 => Progress::Unsubmitted,
+                Some(_) => Progress::Solved,
+            };
+            (day, stars, progress)
+        })
+        .collect()
+}
+
+pub fn status_grid(rows: &[(u8, u8, Progress)]) -> String {
+    rows.iter()
+        .map(|(day, stars, progress)| {
+            let stars = "*".repeat(*stars as usize);
+            format!("day {day:>2}  {stars:<2}  {progress}\n")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../tests/fixtures/calendar.html");
+
+    #[test]
+    fn tparse() {
+        let stars = parse_calendar(FIXTURE);
+        assert_eq!(stars.get(&1), Some(&2));
+        assert_eq!(stars.get(&10), Some(&1));
+        assert_eq!(stars.get(&11), Some(&0));
+        assert_eq!(stars.get(&12), Some(&0));
+        assert_eq!(stars.get(&5), None);
+    }
+
+    // the fixture's progress depends on which days are registered
+    #[cfg(feature = "all-days")]
+    #[test]
+    fn tprogress() {
+        let rows = progress(&parse_calendar(FIXTURE));
+        assert_eq!(rows.len(), DAYS as usize);
+        assert_eq!(rows[0], (1, 2, Progress::Solved));
+        assert_eq!(rows[4], (5, 0, Progress::Unsubmitted));
+        assert_eq!(rows[9], (10, 1, Progress::Placeholder));
+        assert_eq!(rows[10], (11, 0, Progress::Missing));
+    }
+
+    #[cfg(feature = "all-days")]
+    #[test]
+    fn tgrid() {
+        let grid = status_grid(&progress(&parse_calendar(FIXTURE)));
+        let lines: Vec<&str> = grid.lines().collect();
+        assert_eq!(lines[0], "day  1  **  solved");
+        assert_eq!(lines[9], "day 10  *   placeholder");
+        assert_eq!(lines[11], "day 12      missing");
+    }
+}
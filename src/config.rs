@@ -0,0 +1,253 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    ffi::OsString,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::{LazyLock, OnceLock},
+};
+
+use anyhow::{Context, bail};
+use serde::Deserialize;
+
+const MANIFEST_DIR: &str = env!("CARGO_MANIFEST_DIR");
+
+/// Settings from `aoc.toml` in the crate root, or wherever `AOC_CONFIG` points.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub cache_dir: Option<PathBuf>,
+    /// Gzip newly cached inputs and puzzle pages.
+    pub compress: bool,
+    /// Never touch the network; see `fetch::offline`.
+    pub offline: bool,
+    pub profiles: BTreeMap<String, Profile>,
+    /// Directory the config file was read from; relative paths are resolved against it.
+    #[serde(skip)]
+    pub root: PathBuf,
+}
+
+/// Someone else's account, for running against their inputs: `[profiles.<name>]`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    /// Falls back to the `SESSION_<NAME>` env var.
+    pub session: Option<String>,
+    /// Defaults to a `<name>` subdirectory of the usual cache dir.
+    pub cache_dir: Option<PathBuf>,
+    /// Day number -> `[part1, part2]` answers for this account's input.
+    pub answers: BTreeMap<String, Vec<String>>,
+}
+
+static NO_PROFILE: Profile = Profile {
+    session: None,
+    cache_dir: None,
+    answers: BTreeMap::new(),
+};
+
+static ACTIVE_PROFILE: OnceLock<Option<String>> = OnceLock::new();
+
+static CONFIG: LazyLock<Config> = LazyLock::new(|| {
+    Config::load().unwrap_or_else(|e| {
+        // it may have said `offline = true`, so don't risk the network
+        eprintln!("ignoring config and staying offline: {e:#}");
+        Config {
+            offline: true,
+            ..Config::default()
+        }
+    })
+});
+
+pub fn config() -> &'static Config {
+    &CONFIG
+}
+
+/// Switch to a named profile, like `AOC_PROFILE`; must happen before anything
+/// is fetched.
+pub fn set_profile(name: &str) -> Result<(), anyhow::Error> {
+    if ACTIVE_PROFILE.set(Some(name.to_string())).is_err() {
+        bail!("profile already chosen");
+    }
+
+    Ok(())
+}
+
+/// The profile picked by `set_profile` or `AOC_PROFILE`, if any. Profiles
+/// that aren't in the config still get their own cache dir and session var.
+pub fn active_profile() -> Option<(&'static str, &'static Profile)> {
+    let name = ACTIVE_PROFILE
+        .get_or_init(|| env::var("AOC_PROFILE").ok().filter(|p| !p.is_empty()))
+        .as_deref()?;
+
+    Some((name, config().profiles.get(name).unwrap_or(&NO_PROFILE)))
+}
+
+pub(crate) fn session() -> Result<String, anyhow::Error> {
+    let Some((name, profile)) = active_profile() else {
+        return env::var("SESSION").context("SESSION env var is required to fetch input");
+    };
+
+    let var = format!("SESSION_{}", name.to_uppercase());
+    profile
+        .session
+        .clone()
+        .or_else(|| env::var(&var).ok())
+        .with_context(|| format!("profile {name} needs a session in the config or {var}"))
+}
+
+/// What `part` of `day` should come to: `default` normally, or the active
+/// profile's recorded answer, which may not be known yet.
+pub fn expected_answer(day: u8, part: u8, default: &str) -> Option<String> {
+    let Some((_, profile)) = active_profile() else {
+        return Some(default.to_string());
+    };
+
+    profile
+        .answers
+        .get(&day.to_string())?
+        .get(part as usize - 1)
+        .cloned()
+}
+
+impl Config {
+    pub fn path() -> PathBuf {
+        env::var_os("AOC_CONFIG")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| Path::new(MANIFEST_DIR).join("aoc.toml"))
+    }
+
+    pub fn load() -> Result<Config, anyhow::Error> {
+        let path = Config::path();
+        let root = path.parent().unwrap_or(Path::new(".")).to_owned();
+
+        match fs::read_to_string(&path) {
+            Ok(s) => Config::parse(&s, root).with_context(|| format!("in {}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Config {
+                root,
+                ..Config::default()
+            }),
+            Err(e) => Err(e).with_context(|| format!("reading {}", path.display())),
+        }
+    }
+
+    pub fn parse(s: &str, root: PathBuf) -> Result<Config, anyhow::Error> {
+        let mut c: Config = toml::from_str(s)?;
+        c.root = root;
+        Ok(c)
+    }
+}
+
+/// Where cached inputs live: `AOC_CACHE_DIR`, then `cache_dir` from the
+/// config, then `inputs/` in the crate root, or the XDG cache dir if the
+/// binary has been moved away from its source tree. Profiles get their own
+/// directory inside that unless they name one.
+pub fn cache_dir() -> PathBuf {
+    resolve_cache_dir(env::var_os("AOC_CACHE_DIR"), config(), active_profile())
+}
+
+fn resolve_cache_dir(
+    from_env: Option<OsString>,
+    config: &Config,
+    profile: Option<(&str, &Profile)>,
+) -> PathBuf {
+    let base = base_cache_dir(from_env, config);
+    match profile {
+        Some((
+            _,
+            Profile {
+                cache_dir: Some(dir),
+                ..
+            },
+        )) => config.root.join(dir),
+        Some((name, _)) => base.join(name),
+        None => base,
+    }
+}
+
+fn base_cache_dir(from_env: Option<OsString>, config: &Config) -> PathBuf {
+    if let Some(dir) = from_env {
+        return PathBuf::from(dir);
+    }
+    if let Some(dir) = &config.cache_dir {
+        return config.root.join(dir);
+    }
+
+    let manifest = Path::new(MANIFEST_DIR);
+    if manifest.is_dir() {
+        return manifest.join("inputs");
+    }
+
+    env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|h| PathBuf::from(h).join(".cache")))
+        .unwrap_or_else(env::temp_dir)
+        .join("aoc25-rs")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tparse() {
+        let c = Config::parse("cache_dir = \"cache\"\n", PathBuf::from("/x")).unwrap();
+        assert_eq!(c.cache_dir, Some(PathBuf::from("cache")));
+        assert!(Config::parse("cache_dri = \"oops\"", PathBuf::new()).is_err());
+    }
+
+    #[test]
+    fn tresolve() {
+        let none = Config::default();
+        let rel = Config::parse("cache_dir = \"cache\"", PathBuf::from("/x")).unwrap();
+        let abs = Config::parse("cache_dir = \"/abs\"", PathBuf::from("/x")).unwrap();
+
+        assert_eq!(
+            resolve_cache_dir(None, &none, None),
+            Path::new(MANIFEST_DIR).join("inputs")
+        );
+        assert_eq!(
+            resolve_cache_dir(None, &rel, None),
+            PathBuf::from("/x/cache")
+        );
+        assert_eq!(resolve_cache_dir(None, &abs, None), PathBuf::from("/abs"));
+        assert_eq!(
+            resolve_cache_dir(Some("/env".into()), &rel, None),
+            PathBuf::from("/env")
+        );
+    }
+
+    const PROFILES: &str = r#"
+        cache_dir = "cache"
+
+        [profiles.andrew]
+        answers = { 1 = ["1234", "5678"], 2 = ["99"] }
+
+        [profiles.sam]
+        session = "53616c74"
+        cache_dir = "/elsewhere/sam"
+    "#;
+
+    #[test]
+    fn tprofiles() {
+        let c = Config::parse(PROFILES, PathBuf::from("/x")).unwrap();
+        let andrew = &c.profiles["andrew"];
+        let sam = &c.profiles["sam"];
+
+        assert_eq!(andrew.answers["1"], ["1234", "5678"]);
+        assert_eq!(sam.session.as_deref(), Some("53616c74"));
+
+        assert_eq!(
+            resolve_cache_dir(None, &c, Some(("andrew", andrew))),
+            PathBuf::from("/x/cache/andrew")
+        );
+        assert_eq!(
+            resolve_cache_dir(Some("/env".into()), &c, Some(("andrew", andrew))),
+            PathBuf::from("/env/andrew")
+        );
+        assert_eq!(
+            resolve_cache_dir(None, &c, Some(("sam", sam))),
+            PathBuf::from("/elsewhere/sam")
+        );
+    }
+}
@@ -1,17 +1,42 @@
-use crate::{Answer, Day};
+use crate::{
+    Answer, Budget, Day, Observer,
+    parse::{Mode, ParseError, Span, int, lines, normalize},
+};
 
 pub struct Day1 {}
 
-#[derive(Debug, PartialEq, Eq)]
-enum Direction {
+/// One rotation of the dial, by a number of clicks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
     Left(isize),
     Right(isize),
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct Params {
+    /// Where the dial points before the first rotation.
+    pub start: isize,
+    /// Number of clicks round the dial.
+    pub dial: isize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            start: 50,
+            dial: 100,
+        }
+    }
+}
+
 impl Day for Day1 {
-    fn part1(input: &str) -> impl Answer {
-        let steps = parse(input);
-        let mut curr = 50;
+    type Params = Params;
+
+    const ANSWERS: [&str; 2] = ["1177", "6768"];
+
+    fn part1_with(input: &str, params: Params, _: impl Observer, _: Budget) -> impl Answer {
+        let steps = parse(input.as_bytes()).expect("bad input");
+        let mut curr = params.start;
         let mut zeroes = 0;
 
         for dir in steps {
@@ -19,7 +44,7 @@ impl Day for Day1 {
                 Direction::Left(c) => curr -= c,
                 Direction::Right(c) => curr += c,
             }
-            if (curr % 100) == 0 {
+            if (curr % params.dial) == 0 {
                 zeroes += 1
             }
         }
@@ -27,9 +52,9 @@ impl Day for Day1 {
         zeroes
     }
 
-    fn part2(input: &str) -> impl Answer {
-        let steps = parse(input);
-        let mut curr = 50;
+    fn part2_with(input: &str, params: Params, _: impl Observer, _: Budget) -> impl Answer {
+        let steps = parse(input.as_bytes()).expect("bad input");
+        let mut curr = params.start;
         let mut zeroes = 0;
 
         for dir in steps {
@@ -38,8 +63,8 @@ impl Day for Day1 {
                 Direction::Right(c) => (c, 1),
             };
 
-            while c.abs() > 100 {
-                c -= 100 * by;
+            while c.abs() > params.dial {
+                c -= params.dial * by;
                 zeroes += 1;
             }
 
@@ -47,7 +72,7 @@ impl Day for Day1 {
                 curr += by;
                 c -= by;
 
-                if curr % 100 == 0 {
+                if curr % params.dial == 0 {
                     zeroes += 1
                 }
             }
@@ -57,18 +82,17 @@ impl Day for Day1 {
     }
 }
 
-fn parse(input: &str) -> Vec<Direction> {
-    input
-        .trim()
-        .lines()
+/// The rotations, one per line like `L68`.
+pub fn parse(input: &[u8]) -> Result<Vec<Direction>, ParseError> {
+    let input = normalize(input, Mode::TEXT);
+    lines(Span::new(&input))
         .map(|line| {
-            let (l, r) = line.trim().split_at(1);
-            let r: isize = r.parse().expect("bad num");
-            match l {
-                "L" => Direction::Left(r),
-                "R" => Direction::Right(r),
-                _ => panic!("bad direction"),
-            }
+            let dir = match line.first() {
+                Some(b'L') => Direction::Left,
+                Some(b'R') => Direction::Right,
+                _ => return Err(line.error("expected L or R")),
+            };
+            Ok(dir(int(line.slice(1, line.len()))?))
         })
         .collect()
 }
@@ -77,7 +101,7 @@ fn parse(input: &str) -> Vec<Direction> {
 mod test {
     use super::*;
 
-    const TEST_INPUT: &'static str = "L68
+    const TEST_INPUT: &str = "L68
     L30
     R48
     L5
@@ -90,7 +114,7 @@ mod test {
 
     #[test]
     fn p() {
-        let steps = parse("L2\nR82\nL10");
+        let steps = parse(b"L2\nR82\nL10").unwrap();
         assert_eq!(
             steps,
             vec![
@@ -108,10 +132,15 @@ mod test {
     }
 
     #[test]
-    fn andrew_test() {
-        let input = std::fs::read_to_string("inputs/AB_1.txt").expect("Missing Input");
-        let res = Day1::part2(&input);
-
-        assert_eq!(res.to_string(), "💣");
+    fn tparams() {
+        let small = Params { start: 0, dial: 10 };
+        assert_eq!(
+            Day1::part1_with("R10\nL5\nL15", small, (), Budget::new()).to_string(),
+            "2"
+        );
+        assert_eq!(
+            Day1::part2_with("R25\nL5", small, (), Budget::new()).to_string(),
+            "3"
+        );
     }
 }
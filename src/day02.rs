@@ -1,74 +1,93 @@
 use std::{collections::HashSet, ops::RangeInclusive};
 
-use crate::{Answer, Day};
+use crate::{
+    Answer, Arith, Budget, Day, Int, Observer,
+    num::with_int,
+    parse::{Mode, ParseError, Span, normalize, range},
+};
 
 pub struct Day2 {}
 
 impl Day for Day2 {
-    fn part1(input: &str) -> impl Answer {
-        let ranges = parse(input);
-        ranges
-            .into_iter()
-            .flat_map(|r| {
-                let mut h = HashSet::<u64>::new();
-                n_repeats(&r, 2, &mut h);
-                h
-            })
-            .sum::<u64>()
-    }
-
-    fn part2(input: &str) -> impl Answer {
-        let ranges = parse(input);
-        ranges.into_iter().flat_map(any_repeats).sum::<u64>()
+    type Params = ();
+    const ANSWERS: [&str; 2] = ["12850231731", "24774350322"];
+    const ARITH: [bool; 2] = [true, true];
+
+    fn part1_with(input: &str, _: (), _: impl Observer, _: Budget) -> impl Answer {
+        solve1::<u64>(input)
+    }
+
+    fn part2_with(input: &str, _: (), _: impl Observer, _: Budget) -> impl Answer {
+        solve2::<u64>(input)
+    }
+
+    fn part1_as(input: &str, arith: Arith, _: Budget) -> String {
+        with_int!(arith, u64, solve1(input))
+    }
+
+    fn part2_as(input: &str, arith: Arith, _: Budget) -> String {
+        with_int!(arith, u64, solve2(input))
     }
 }
 
-fn parse(input: &str) -> Vec<RangeInclusive<u64>> {
-    input
-        .trim()
-        .split(",")
-        .filter_map(|pair| -> Option<RangeInclusive<u64>> {
-            if let Some((l, r)) = pair.trim().split_once("-")
-                && let Ok(l) = l.parse()
-                && let Ok(r) = r.parse()
-            {
-                return Some(l..=r);
-            }
-
-            None
+fn solve1<N: Int>(input: &str) -> N {
+    let ranges = parse(input.as_bytes()).expect("bad input");
+    ranges
+        .into_iter()
+        .flat_map(|r| {
+            let mut h = HashSet::<N>::new();
+            n_repeats(&r, 2, &mut h);
+            h
         })
+        .sum()
+}
+
+fn solve2<N: Int>(input: &str) -> N {
+    let ranges = parse(input.as_bytes()).expect("bad input");
+    ranges.into_iter().flat_map(any_repeats).sum()
+}
+
+/// The comma-separated ID ranges, like `11-22,95-115`.
+pub fn parse<N: Int>(input: &[u8]) -> Result<Vec<RangeInclusive<N>>, ParseError> {
+    let input = normalize(input, Mode::TEXT);
+    Span::new(&input)
+        .trim()
+        .split(b',')
+        .map(|pair| pair.trim())
+        .filter(|pair| !pair.is_empty())
+        .map(range)
         .collect()
 }
 
-fn count_digits(i: u64) -> u64 {
+fn count_digits<N: Int>(i: N) -> u32 {
+    let [e2, e4, e8] = [100, 10000, 100000000].map(N::from_usize);
     let mut n = 1;
     let mut i = i;
-    while i >= 100000000 {
+    while i >= e8 {
         n += 8;
-        i /= 100000000;
+        i = i / e8;
     }
-    if i >= 10000 {
+    if i >= e4 {
         n += 4;
-        i /= 10000;
+        i = i / e4;
     }
-    if i >= 100 {
+    if i >= e2 {
         n += 2;
-        i /= 100;
+        i = i / e2;
     }
-    if i >= 10 {
+    if i >= N::from_usize(10) {
         n += 1;
     }
 
     n
 }
 
-fn to_next_digit_count(n: u64) -> u64 {
-    let d = count_digits(n);
-    10u64.pow(d as u32)
+fn to_next_digit_count<N: Int>(n: N) -> N {
+    N::from_usize(10).pow(count_digits(n))
 }
 
-fn n_repeats(r: &RangeInclusive<u64>, repeats: u64, outs: &mut HashSet<u64>) {
-    let r1: u32 = repeats as u32 - 1;
+fn n_repeats<N: Int>(r: &RangeInclusive<N>, repeats: u32, outs: &mut HashSet<N>) {
+    let r1 = repeats - 1;
     let end = *r.end();
     let mut check = *r.start();
 
@@ -83,7 +102,7 @@ fn n_repeats(r: &RangeInclusive<u64>, repeats: u64, outs: &mut HashSet<u64>) {
         }
 
         // factor is the 10^ size of our repeated segment
-        let factor = 10u64.pow((d / repeats) as u32);
+        let factor = N::from_usize(10).pow(d / repeats);
         // and base is the repeated segment we're looking for
         let base = check / factor.pow(r1);
 
@@ -93,7 +112,7 @@ fn n_repeats(r: &RangeInclusive<u64>, repeats: u64, outs: &mut HashSet<u64>) {
         // now we repeatedly *factor+base, leaving us with e.g. 121212
         // we preemptively compute our next check at the same time
         let mut result = base;
-        let base1 = base + 1;
+        let base1 = base + N::ONE;
         let mut next_result = base1;
         for _ in 0..r1 {
             result = result * factor + base;
@@ -114,8 +133,8 @@ fn n_repeats(r: &RangeInclusive<u64>, repeats: u64, outs: &mut HashSet<u64>) {
     }
 }
 
-fn any_repeats(r: RangeInclusive<u64>) -> impl IntoIterator<Item = u64> {
-    let mut out = HashSet::<u64>::new();
+fn any_repeats<N: Int>(r: RangeInclusive<N>) -> impl IntoIterator<Item = N> {
+    let mut out = HashSet::<N>::new();
 
     let digits = count_digits(*r.end());
     for ds in 2..=digits {
@@ -127,13 +146,13 @@ fn any_repeats(r: RangeInclusive<u64>) -> impl IntoIterator<Item = u64> {
 
 #[cfg(test)]
 mod tests {
-    use crate::day02::*;
+    use crate::{Checked, day02::*};
 
-    const TEST_INPUT: &'static str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
+    const TEST_INPUT: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
 
     #[test]
     fn parsing() {
-        let v = parse(TEST_INPUT);
+        let v = parse::<u64>(TEST_INPUT.as_bytes()).unwrap();
         assert_eq!(v[0], 11..=22);
         assert_eq!(*v.last().unwrap(), 2121212118..=2121212124);
     }
@@ -146,7 +165,7 @@ mod tests {
         assert_eq!(count_digits(99999), 5);
     }
 
-    fn run_n_repeats(r: RangeInclusive<u64>, repeats: u64) -> Vec<u64> {
+    fn run_n_repeats(r: RangeInclusive<u64>, repeats: u32) -> Vec<u64> {
         let mut h = HashSet::<u64>::new();
         n_repeats(&r, repeats, &mut h);
 
@@ -183,6 +202,20 @@ mod tests {
         assert_eq!(Day2::part2(TEST_INPUT).to_string(), "4174379265");
     }
 
+    #[test]
+    fn arith() {
+        for arith in [Arith::Checked, Arith::Wide] {
+            assert_eq!(
+                Day2::part2_as(TEST_INPUT, arith, Budget::new()),
+                "4174379265"
+            );
+        }
+        // the candidate after 4294942949 is past u32::MAX
+        let near_max = "4294967000-4294967295";
+        assert_eq!(solve1::<u64>(near_max), 0);
+        assert!(std::panic::catch_unwind(|| solve1::<Checked<u32>>(near_max)).is_err());
+    }
+
     #[test]
     fn test_n_repeats() {
         let mut outs = HashSet::<u64>::new();
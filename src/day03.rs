@@ -1,55 +1,83 @@
-use crate::Day;
-
-type Int = isize;
+use crate::{
+    Arith, Budget, Day, Int, Observer,
+    num::with_int,
+    parse::{Mode, ParseError, Span, digits, lines, normalize},
+};
 
 pub struct Day3 {}
 
+#[derive(Debug, Clone, Copy)]
+pub struct Params {
+    /// Batteries turned on per bank in part 2.
+    pub digits: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { digits: 12 }
+    }
+}
+
 impl Day for Day3 {
-    fn part1(input: &str) -> impl crate::Answer {
-        let inp = parse(input);
-        inp.into_iter()
-            .map(|line| {
-                let (loc, d1) = max_in_slice(&line[0..line.len() - 1]);
-                let (_, d2) = max_in_slice(&line[loc + 1..]);
-
-                d1 * 10 + d2
-            })
-            .sum::<Int>()
+    type Params = Params;
+
+    const ANSWERS: [&str; 2] = ["17554", "175053592950232"];
+    const ARITH: [bool; 2] = [true, true];
+
+    fn part1_with(input: &str, _: Params, _: impl Observer, _: Budget) -> impl crate::Answer {
+        solve1::<isize>(input)
+    }
+
+    fn part2_with(input: &str, params: Params, _: impl Observer, _: Budget) -> impl crate::Answer {
+        solve2::<isize>(input, params)
+    }
+
+    fn part1_as(input: &str, arith: Arith, _: Budget) -> String {
+        with_int!(arith, isize, solve1(input))
     }
 
-    fn part2(input: &str) -> impl crate::Answer {
-        let inp = parse(input);
-        inp.into_iter()
-            .map(|line| {
-                let mut s = 0;
-                let mut last_loc = 0;
-                for i in (0..12).rev() {
-                    let (loc, n) = max_in_slice(&line[last_loc..(line.len() - i)]);
-                    s = s * 10 + n;
-                    last_loc = last_loc + loc + 1;
-                }
-
-                s
-            })
-            .sum::<Int>()
+    fn part2_as(input: &str, arith: Arith, _: Budget) -> String {
+        with_int!(arith, isize, solve2(input, Params::default()))
     }
 }
 
-fn parse(input: &str) -> Vec<Vec<Int>> {
-    input
-        .trim()
-        .lines()
-        .map(|l| {
-            l.trim()
-                .as_bytes()
-                .iter()
-                .map(|b| (b - 48) as Int)
-                .collect()
+fn solve1<N: Int>(input: &str) -> N {
+    let inp = parse(input.as_bytes()).expect("bad input");
+    inp.into_iter()
+        .map(|line| {
+            let (loc, d1) = max_in_slice(&line[0..line.len() - 1]);
+            let (_, d2) = max_in_slice(&line[loc + 1..]);
+
+            N::from_usize(d1 as usize * 10 + d2 as usize)
         })
-        .collect()
+        .sum()
+}
+
+fn solve2<N: Int>(input: &str, params: Params) -> N {
+    let inp = parse(input.as_bytes()).expect("bad input");
+    let ten = N::from_usize(10);
+    inp.into_iter()
+        .map(|line| {
+            let mut s = N::ZERO;
+            let mut last_loc = 0;
+            for i in (0..params.digits).rev() {
+                let (loc, n) = max_in_slice(&line[last_loc..(line.len() - i)]);
+                s = s * ten + N::from_usize(n as usize);
+                last_loc = last_loc + loc + 1;
+            }
+
+            s
+        })
+        .sum()
+}
+
+/// Each bank's battery joltages, one digit per battery.
+pub fn parse(input: &[u8]) -> Result<Vec<Vec<u8>>, ParseError> {
+    let input = normalize(input, Mode::TEXT);
+    lines(Span::new(&input)).map(digits).collect()
 }
 
-fn max_in_slice(slice: &[Int]) -> (usize, Int) {
+fn max_in_slice(slice: &[u8]) -> (usize, u8) {
     // all Rust's iter::max(_by) let last equal element win;
     // we need the first
     let mut curr = (0, slice[0]);
@@ -66,7 +94,7 @@ fn max_in_slice(slice: &[Int]) -> (usize, Int) {
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &'static str = "
+    const TEST_INPUT: &str = "
         987654321111111
         811111111111119
         234234234234278
@@ -74,7 +102,7 @@ mod tests {
 
     #[test]
     fn tparse() {
-        let i = parse(TEST_INPUT);
+        let i = parse(TEST_INPUT.as_bytes()).unwrap();
         assert_eq!(i[0][0], 9);
         assert_eq!(i[1][0], 8);
         assert_eq!(*i[3].last().unwrap(), 1);
@@ -98,5 +126,9 @@ mod tests {
     #[test]
     fn p2() {
         assert_eq!(Day3::part2(TEST_INPUT).to_string(), "3121910778619");
+        assert_eq!(
+            Day3::part2_with(TEST_INPUT, Params { digits: 2 }, (), Budget::new()).to_string(),
+            "357"
+        );
     }
 }
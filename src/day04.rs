@@ -1,13 +1,17 @@
 use crate::{
-    Day,
+    Budget, Day, Event, Observer,
     intmap::{Coord, IntMap},
+    parse::{Mode, ParseError, Span, grid, normalize},
 };
 
 pub struct Day4 {}
 
 impl Day for Day4 {
-    fn part1(input: &str) -> impl crate::Answer {
-        let m = parse(input);
+    type Params = ();
+    const ANSWERS: [&str; 2] = ["1428", "8936"];
+
+    fn part1_with(input: &str, _: (), _: impl Observer, _: Budget) -> impl crate::Answer {
+        let m = parse(input.as_bytes()).expect("bad input");
         m.iter()
             .map(|co| {
                 let Some(c) = m.get(co) else {
@@ -26,14 +30,24 @@ impl Day for Day4 {
             .sum::<isize>()
     }
 
-    fn part2(input: &str) -> impl crate::Answer {
-        let mut m = parse(input);
+    fn part2_with(
+        input: &str,
+        _: (),
+        mut obs: impl Observer,
+        budget: Budget,
+    ) -> impl crate::Answer {
+        let mut m = parse(input.as_bytes()).expect("bad input");
         let mut removed = 0;
         let mut removed_this_round = 0;
 
         let cos: Vec<_> = m.iter().collect();
 
-        loop {
+        for round in 1.. {
+            // a round is a step; stopping early undercounts
+            if !budget.step() {
+                break;
+            }
+
             for &co in cos.iter() {
                 if let Some(Cell::Paper) = m.get(co)
                     && count_adjacent_paper(&m, co) < 4
@@ -43,6 +57,14 @@ impl Day for Day4 {
                 }
             }
 
+            if obs.enabled() {
+                obs.event(Event::Removed {
+                    round,
+                    removed: removed_this_round,
+                    grid: render(&m),
+                });
+            }
+
             if removed_this_round == 0 {
                 break;
             } else {
@@ -54,18 +76,36 @@ impl Day for Day4 {
     }
 }
 
+/// A spot in the grid: `.` or a roll of paper `@`.
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
-enum Cell {
+pub enum Cell {
     #[default]
     Empty,
     Paper,
 }
 
-fn parse(input: &str) -> IntMap<Cell> {
-    IntMap::from_raw(input, |c| match c {
-        '.' => Cell::Empty,
-        '@' => Cell::Paper,
-        _ => panic!("bad cell"),
+fn render(m: &IntMap<Cell>) -> String {
+    m.cells
+        .chunks(m.width)
+        .map(|row| {
+            row.iter()
+                .map(|c| match c {
+                    Cell::Empty => '.',
+                    Cell::Paper => '@',
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The grid of `.` and `@` rolls of paper.
+pub fn parse(input: &[u8]) -> Result<IntMap<Cell>, ParseError> {
+    let input = normalize(input, Mode::TEXT);
+    grid(Span::new(&input), |c| match c {
+        b'.' => Some(Cell::Empty),
+        b'@' => Some(Cell::Paper),
+        _ => None,
     })
 }
 
@@ -104,7 +144,7 @@ fn count_adjacent_paper(m: &IntMap<Cell>, co: (isize, isize)) -> isize {
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &'static str = "
+    const TEST_INPUT: &str = "
         ..@@.@@@@.
         @@@.@.@.@@
         @@@@@.@.@@
@@ -119,7 +159,7 @@ mod tests {
 
     #[test]
     fn tparse() {
-        let m = parse(TEST_INPUT);
+        let m = parse(TEST_INPUT.as_bytes()).unwrap();
         assert_eq!(m.get((0, 0)), Some(&Cell::Empty));
         assert_eq!(m.get((0, 1)), Some(&Cell::Paper));
         assert_eq!(m.get((0, 9)), Some(&Cell::Paper));
@@ -151,4 +191,35 @@ mod tests {
     fn tp2() {
         assert_eq!(Day4::part2(TEST_INPUT).to_string(), 43.to_string());
     }
+
+    #[test]
+    fn tbudget() {
+        let budget = Budget::steps(1);
+        let removed = Day4::part2_with(TEST_INPUT, (), (), budget.clone());
+        // just the first round
+        assert_eq!(removed.to_string(), "30");
+        assert!(budget.stopped());
+    }
+
+    #[test]
+    fn tevents() {
+        let mut events = vec![];
+        Day4::part2_with(TEST_INPUT, (), &mut events, Budget::new());
+
+        let mut total = 0;
+        for (i, e) in events.iter().enumerate() {
+            let Event::Removed { round, removed, .. } = e else {
+                panic!("unexpected {e:?}");
+            };
+            assert_eq!(*round, i + 1);
+            total += removed;
+        }
+        assert_eq!(total, 43);
+
+        let Some(Event::Removed { removed, grid, .. }) = events.last() else {
+            panic!("no rounds");
+        };
+        assert_eq!(*removed, 0);
+        assert_eq!(grid.lines().next(), Some(".........."));
+    }
 }
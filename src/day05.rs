@@ -1,18 +1,24 @@
-use core::slice::GetDisjointMutIndex;
 use std::{
     cmp::{max, min},
     ops::RangeInclusive,
 };
 
-use crate::Day;
-
-type Int = usize;
+use crate::{
+    Arith, Budget, Day, Int, Observer,
+    num::with_int,
+    parse::{Mode, ParseError, Span, blocks, int, lines, normalize, range},
+    util::overlaps,
+};
 
 pub struct Day5 {}
 
 impl Day for Day5 {
-    fn part1(input: &str) -> impl crate::Answer {
-        let db = parse(input);
+    type Params = ();
+    const ANSWERS: [&str; 2] = ["558", "344813017450467"];
+    const ARITH: [bool; 2] = [false, true];
+
+    fn part1_with(input: &str, _: (), _: impl Observer, _: Budget) -> impl crate::Answer {
+        let db = parse::<usize>(input.as_bytes()).expect("bad input");
 
         db.ingredients
             .into_iter()
@@ -20,70 +26,76 @@ impl Day for Day5 {
             .count()
     }
 
-    fn part2(input: &str) -> impl crate::Answer {
-        let db = parse(input);
-        let mut merged_ranges: Vec<RangeInclusive<Int>> = vec![];
-
-        'outer: for r in db.fresh_ranges {
-            for existing in merged_ranges.iter_mut() {
-                if r.is_overlapping(existing) {
-                    let l = min(*r.start(), *existing.start());
-                    let h = max(*r.end(), *existing.end());
-                    *existing = l..=h;
-                    continue 'outer;
-                }
+    fn part2_with(input: &str, _: (), _: impl Observer, _: Budget) -> impl crate::Answer {
+        solve2::<usize>(input)
+    }
+
+    fn part2_as(input: &str, arith: Arith, _: Budget) -> String {
+        with_int!(arith, usize, solve2(input))
+    }
+}
+
+fn solve2<N: Int>(input: &str) -> N {
+    let db = parse::<N>(input.as_bytes()).expect("bad input");
+    let mut merged_ranges: Vec<RangeInclusive<N>> = vec![];
+
+    'outer: for r in db.fresh_ranges {
+        for existing in merged_ranges.iter_mut() {
+            if overlaps(&r, existing) {
+                let l = min(*r.start(), *existing.start());
+                let h = max(*r.end(), *existing.end());
+                *existing = l..=h;
+                continue 'outer;
             }
-            merged_ranges.push(r);
         }
-
-        merged_ranges
-            .iter()
-            .map(|r| (*r.end() - *r.start()) + 1)
-            .sum::<usize>()
+        merged_ranges.push(r);
     }
+
+    merged_ranges
+        .iter()
+        .map(|r| (*r.end() - *r.start()) + N::ONE)
+        .sum()
 }
 
-struct DB {
-    pub fresh_ranges: Vec<RangeInclusive<Int>>,
-    pub ingredients: Vec<Int>,
+/// The ingredient database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DB<N> {
+    /// Sorted by start, possibly overlapping.
+    pub fresh_ranges: Vec<RangeInclusive<N>>,
+    pub ingredients: Vec<N>,
 }
 
-fn parse_range(input: &str) -> RangeInclusive<Int> {
-    let (a, b) = input.trim().split_once("-").expect("need a -");
-    if a <= b {
-        a.parse().expect("left is num")..=b.parse().expect("right is num")
-    } else {
-        b.parse().expect("left is num")..=a.parse().expect("right is num")
-    }
+fn parse_range<N: Int>(input: Span) -> Result<RangeInclusive<N>, ParseError> {
+    let (a, b) = range::<N>(input)?.into_inner();
+    Ok(a.min(b)..=a.max(b))
 }
 
-fn parse(input: &str) -> DB {
-    let (fresh, ingred) = input
-        .trim()
-        .split_once("\n\n")
-        .expect("found more than one double nl");
+/// Fresh ID ranges, a blank line, then available ingredient IDs.
+pub fn parse<N: Int>(input: &[u8]) -> Result<DB<N>, ParseError> {
+    let input = normalize(input, Mode::TEXT);
+    let input = Span::new(&input);
+    let [fresh, ingred] = blocks(input)[..] else {
+        return Err(input.error("expected ranges and ingredients separated by a blank line"));
+    };
 
-    let mut fresh_ranges: Vec<RangeInclusive<Int>> =
-        fresh.trim().lines().map(parse_range).collect();
-    let ingredients: Vec<Int> = ingred
-        .trim()
-        .lines()
-        .map(|l| l.trim().parse().expect("should be num"))
-        .collect();
+    let mut fresh_ranges = lines(fresh)
+        .map(parse_range)
+        .collect::<Result<Vec<_>, _>>()?;
+    let ingredients = lines(ingred).map(int).collect::<Result<Vec<N>, _>>()?;
 
     fresh_ranges.sort_by_key(|r| *r.start());
 
-    DB {
+    Ok(DB {
         fresh_ranges,
         ingredients,
-    }
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &'static str = "
+    const TEST_INPUT: &str = "
     3-5
     10-14
     16-20
@@ -98,7 +110,7 @@ mod tests {
 
     #[test]
     fn tparse() {
-        let d = parse(TEST_INPUT);
+        let d = parse::<usize>(TEST_INPUT.as_bytes()).unwrap();
 
         assert!(d.fresh_ranges.starts_with(&[3..=5, 10..=14]));
         assert!(d.fresh_ranges.ends_with(&[16..=20]));
@@ -114,5 +126,10 @@ mod tests {
     #[test]
     fn t2() {
         assert_eq!(Day5::part2(TEST_INPUT).to_string(), "14");
+        assert_eq!(
+            Day5::part2_as(TEST_INPUT, Arith::Checked, Budget::new()),
+            "14"
+        );
+        assert_eq!(Day5::part2_as(TEST_INPUT, Arith::Wide, Budget::new()), "14");
     }
 }
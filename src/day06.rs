@@ -1,124 +1,148 @@
 use std::iter::zip;
 
-use crate::Day;
+use crate::{
+    Arith, Budget, Day, Int, Observer,
+    num::with_int,
+    parse::{Mode, ParseError, Span, int, lines, normalize, raw_lines},
+};
 
 pub struct Day6 {}
 
 impl Day for Day6 {
-    fn part1(input: &str) -> impl crate::Answer {
-        let eqs = parse(input);
-        solve_and_sum(eqs)
+    type Params = ();
+    const ANSWERS: [&str; 2] = ["4805473544166", "8907730960817"];
+    const ARITH: [bool; 2] = [true, true];
+
+    fn part1_with(input: &str, _: (), _: impl Observer, _: Budget) -> impl crate::Answer {
+        solve1::<u64>(input)
+    }
+
+    fn part2_with(input: &str, _: (), _: impl Observer, _: Budget) -> impl crate::Answer {
+        solve2::<u64>(input)
     }
 
-    fn part2(input: &str) -> impl crate::Answer {
-        let eqs = parse2(input);
-        solve_and_sum(eqs)
+    fn part1_as(input: &str, arith: Arith, _: Budget) -> String {
+        with_int!(arith, u64, solve1(input))
     }
+
+    fn part2_as(input: &str, arith: Arith, _: Budget) -> String {
+        with_int!(arith, u64, solve2(input))
+    }
+}
+
+fn solve1<N: Int>(input: &str) -> N {
+    let eqs = parse(input.as_bytes()).expect("bad input");
+    solve_and_sum(eqs)
 }
 
-type Int = u64;
+fn solve2<N: Int>(input: &str) -> N {
+    solve_and_sum(parse_columns(input.as_bytes()).expect("bad input"))
+}
 
-#[derive(Debug, PartialEq, Eq)]
-enum Op {
+/// The `+` or `*` under a problem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
     Add,
     Mult,
 }
 
-fn parse(input: &str) -> Vec<(Op, Vec<Int>)> {
-    let mut cols: Vec<Vec<Int>> = vec![];
+/// Each problem's operator and numbers, reading the numbers across rows as
+/// part 1 does.
+pub fn parse<N: Int>(input: &[u8]) -> Result<Vec<(Op, Vec<N>)>, ParseError> {
+    let input = normalize(input, Mode::TEXT);
+    let mut cols: Vec<Vec<N>> = vec![];
     let mut ops: Vec<Op> = vec![];
-    input.trim().lines().for_each(|l| {
-        let l: Vec<&str> = l
-            .trim()
-            .split_ascii_whitespace()
-            .map(|c| c.trim())
-            .collect();
-        let nums: Vec<Int> = l.iter().flat_map(|c| c.parse::<Int>()).collect();
-
-        // dbg!(&l, &nums);
-        if nums.len() == l.len() {
-            for (idx, num) in nums.iter().enumerate() {
+    for l in lines(Span::new(&input)) {
+        let l: Vec<Span> = l.split(b' ').filter(|c| !c.is_empty()).collect();
+
+        if l.iter().all(|c| c.iter().all(u8::is_ascii_digit)) {
+            for (idx, c) in l.into_iter().enumerate() {
+                let num = int(c)?;
                 if let Some(v) = cols.get_mut(idx) {
-                    v.push(*num)
+                    v.push(num)
                 } else {
-                    cols.push(vec![*num]);
+                    cols.push(vec![num]);
                 }
             }
         } else {
             ops = l
                 .into_iter()
-                .map(|c| match c {
-                    "+" => Op::Add,
-                    "*" => Op::Mult,
-                    _ => panic!("bad op"),
+                .map(|c| match c.bytes {
+                    b"+" => Ok(Op::Add),
+                    b"*" => Ok(Op::Mult),
+                    _ => Err(c.error(format!("expected + or *, found {:?}", c.text()))),
                 })
-                .collect();
+                .collect::<Result<_, _>>()?;
         }
-    });
+    }
 
-    zip(ops, cols).collect()
+    Ok(zip(ops, cols).collect())
 }
 
-fn parse2(input: &str) -> Vec<(Op, Vec<Int>)> {
+/// As `parse`, but reading each number down a column, right to left, as
+/// part 2 does. Problems come out rightmost first.
+pub fn parse_columns<N: Int>(input: &[u8]) -> Result<Vec<(Op, Vec<N>)>, ParseError> {
     // ok, whitespace now matters
-    let (l, _) = input.split_once("\n").expect("has a linebreak");
-    let cols = l.len();
-    let lines: Vec<&[u8]> = input.lines().map(|l| l.as_bytes()).collect();
-
-    let mut eqs: Vec<(Op, Vec<Int>)> = vec![];
-    let mut curr_nums: Vec<Int> = vec![];
-    let mut curr_num: Int = 0;
+    let input = normalize(input, Mode::COLUMNS);
+    let lines: Vec<Span> = raw_lines(Span::new(&input)).collect();
+    let Some(first) = lines.first() else {
+        return Err(Span::new(&input).error("empty input"));
+    };
+    let cols = first.len();
+
+    let ten = N::from_usize(10);
+    let mut eqs: Vec<(Op, Vec<N>)> = vec![];
+    let mut curr_nums: Vec<N> = vec![];
+    let mut curr_num = N::ZERO;
 
     for c in (0..cols).rev() {
         for line in &lines {
             let char = line[c];
             match char {
-                0x20 => continue,
-                0x30..=0x39 => curr_num = curr_num * 10 + (char as Int - 0x30),
-                0x2a => {
+                b' ' => continue,
+                b'0'..=b'9' => curr_num = curr_num * ten + N::from_usize((char - b'0') as usize),
+                b'*' => {
                     curr_nums.push(curr_num);
-                    curr_num = 0;
+                    curr_num = N::ZERO;
                     eqs.push((Op::Mult, curr_nums));
                     curr_nums = vec![];
                 }
-                0x2b => {
+                b'+' => {
                     curr_nums.push(curr_num);
-                    curr_num = 0;
+                    curr_num = N::ZERO;
                     eqs.push((Op::Add, curr_nums));
                     curr_nums = vec![];
                 }
                 _ => continue,
             }
-            // dbg!(curr_num);
         }
-        if curr_num != 0 {
-            curr_nums.push(curr_num as Int);
+        if curr_num != N::ZERO {
+            curr_nums.push(curr_num);
         }
-        curr_num = 0;
+        curr_num = N::ZERO;
     }
 
-    eqs
+    Ok(eqs)
 }
 
-fn solve_and_sum(eqs: Vec<(Op, Vec<Int>)>) -> Int {
+fn solve_and_sum<N: Int>(eqs: Vec<(Op, Vec<N>)>) -> N {
     eqs.into_iter()
         .map(|(op, nums)| match op {
-            Op::Add => nums.into_iter().sum::<Int>(),
-            Op::Mult => nums.into_iter().product::<Int>(),
+            Op::Add => nums.into_iter().sum::<N>(),
+            Op::Mult => nums.into_iter().product::<N>(),
         })
-        .sum::<Int>()
+        .sum()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &'static str =
-        "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  ";
+    const TEST_INPUT: &str = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  ";
 
     #[test]
     fn tparse() {
-        let eqs = parse(TEST_INPUT);
+        let eqs = parse::<u64>(TEST_INPUT.as_bytes()).unwrap();
         assert_eq!(eqs.len(), 4);
         assert_eq!(eqs[0].0, Op::Mult);
     }
@@ -129,13 +153,19 @@ mod tests {
     }
 
     #[test]
-    fn tparse2() {
-        let eqs = parse2(TEST_INPUT);
+    fn tparse_columns() {
+        let eqs = parse_columns::<u64>(TEST_INPUT.as_bytes()).unwrap();
         assert_eq!(eqs[0], (Op::Add, vec![4, 431, 623]));
     }
 
     #[test]
     fn t2() {
         assert_eq!(Day6::part2(TEST_INPUT).to_string(), "3263827");
+        let fetched = format!("{TEST_INPUT}\n");
+        assert_eq!(Day6::part2(&fetched).to_string(), "3263827");
+
+        // an editor stripped the trailing spaces
+        let ragged = "123 328  51 64\n 45 64  387 23\n  6 98  215 314\n*   +   *   +";
+        assert_eq!(Day6::part2(ragged).to_string(), "3263827");
     }
 }
@@ -1,12 +1,21 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::{Day, intmap::IntMap};
+use crate::{
+    Arith, Budget, Day, Event, Int, Observer,
+    intmap::IntMap,
+    num::with_int,
+    parse::{Mode, ParseError, Span, grid, normalize},
+};
 
 pub struct Day7 {}
 
 impl Day for Day7 {
-    fn part1(input: &str) -> impl crate::Answer {
-        let m = parse(input);
+    type Params = ();
+    const ANSWERS: [&str; 2] = ["1543", "3223365367809"];
+    const ARITH: [bool; 2] = [false, true];
+
+    fn part1_with(input: &str, _: (), mut obs: impl Observer, _: Budget) -> impl crate::Answer {
+        let m = parse(input.as_bytes()).expect("bad input");
         let mut actives = active(&m, 0);
         let mut splits = 0;
         for row in 0..m.height as isize {
@@ -23,64 +32,102 @@ impl Day for Day7 {
                     }
                 }
             }
+
+            if obs.enabled() {
+                obs.event(Event::Beams {
+                    row: row as usize,
+                    beams: sorted(actives.iter().map(|&col| (col, 1))),
+                });
+            }
         }
 
         splits
     }
 
-    fn part2(input: &str) -> impl crate::Answer {
-        let m = parse(input);
-        let mut actives: HashMap<isize, isize> = active(&m, 0).iter().map(|a| (*a, 1)).collect();
-        // sweep rows
-
-        let rows = 0..m.height as isize;
-        let cols = 0..m.width as isize;
-        for (col, row) in rows.flat_map(|y| cols.clone().map(move |x| (x, y))) {
-            // Beams pass through Beams and Empties so only need to worry about
-            // splitters
-            let c = *m.get((col, row)).unwrap();
-            let col_active = *actives.get(&col).unwrap_or(&0);
-            if c != Cell::Splitter || col_active == 0 {
+    fn part2_with(input: &str, _: (), obs: impl Observer, _: Budget) -> impl crate::Answer {
+        solve2::<isize>(input, obs)
+    }
+
+    fn part2_as(input: &str, arith: Arith, _: Budget) -> String {
+        with_int!(arith, isize, solve2(input, ()))
+    }
+}
+
+/// Timelines reaching the bottom; they double at most splitters, so `N` may
+/// need to be wide.
+fn solve2<N: Int>(input: &str, mut obs: impl Observer) -> N {
+    let m = parse(input.as_bytes()).expect("bad input");
+    let mut actives: HashMap<isize, N> = active(&m, 0).iter().map(|a| (*a, N::ONE)).collect();
+    // sweep rows
+
+    let rows = 0..m.height as isize;
+    let cols = 0..m.width as isize;
+    for (col, row) in rows.flat_map(|y| cols.clone().map(move |x| (x, y))) {
+        if col == 0 && row > 0 && obs.enabled() {
+            obs.event(Event::Beams {
+                row: row as usize - 1,
+                beams: sorted(actives.iter().map(|(&col, &n)| (col, n.to_i128()))),
+            });
+        }
+
+        // Beams pass through Beams and Empties so only need to worry about
+        // splitters
+        let c = *m.get((col, row)).unwrap();
+        let col_active = *actives.get(&col).unwrap_or(&N::ZERO);
+        if c != Cell::Splitter || col_active == N::ZERO {
+            continue;
+        }
+
+        actives.remove(&col);
+        for offset in [col + 1, col - 1] {
+            if !m.in_bounds((offset, row)) {
                 continue;
             }
 
-            actives.remove(&col);
-            for offset in [col + 1, col - 1] {
-                if !m.in_bounds((offset, row)) {
-                    continue;
-                }
-
-                let existing_beams = actives.get_mut(&offset);
-                if let Some(v) = existing_beams {
-                    *v += col_active;
-                } else {
-                    actives.insert(offset, col_active);
-                }
-                // actives.insert(col + 1);
+            let existing_beams = actives.get_mut(&offset);
+            if let Some(v) = existing_beams {
+                *v += col_active;
+            } else {
+                actives.insert(offset, col_active);
             }
+            // actives.insert(col + 1);
         }
+    }
 
-        actives.values().sum::<isize>()
+    if obs.enabled() {
+        obs.event(Event::Beams {
+            row: m.height - 1,
+            beams: sorted(actives.iter().map(|(&col, &n)| (col, n.to_i128()))),
+        });
     }
+
+    actives.values().copied().sum::<N>()
+}
+
+fn sorted(beams: impl Iterator<Item = (isize, i128)>) -> Vec<(isize, i128)> {
+    let mut beams: Vec<_> = beams.collect();
+    beams.sort();
+    beams
 }
 
+/// A spot in the manifold: `.`, a `^` splitter, or a beam, which `S` starts
+/// as.
 #[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
-enum Cell {
+pub enum Cell {
     #[default]
     Empty,
     Splitter,
     Beam,
 }
 
-fn parse(input: &str) -> IntMap<Cell> {
-    IntMap::from_raw(input, |c| match c {
-        '.' => Cell::Empty,
-        '^' => Cell::Splitter,
-        '|' | 'S' => Cell::Beam,
-        _ => {
-            eprintln!("bad char in input {}", c);
-            Cell::Empty
-        }
+/// The manifold; `S` reads as a beam.
+pub fn parse(input: &[u8]) -> Result<IntMap<Cell>, ParseError> {
+    let input = normalize(input, Mode::TEXT);
+    grid(Span::new(&input), |c| match c {
+        b'.' => Some(Cell::Empty),
+        b'^' => Some(Cell::Splitter),
+        b'|' | b'S' => Some(Cell::Beam),
+        _ => None,
     })
 }
 
@@ -98,7 +145,7 @@ mod tests {
 
     use super::*;
 
-    const TEST_INPUT: &'static str = "
+    const TEST_INPUT: &str = "
     .......S.......
     ...............
     .......^.......
@@ -119,8 +166,7 @@ mod tests {
 
     #[test]
     fn tparse() {
-        let m = parse(TEST_INPUT);
-        dbg!(&m);
+        let m = parse(TEST_INPUT.as_bytes()).unwrap();
         assert_eq!(m.get((7, 0)), Some(&Cell::Beam));
     }
 
@@ -134,5 +180,30 @@ mod tests {
     fn t2() {
         let r = Day7::part2(TEST_INPUT);
         assert_eq!(r.to_string(), "40");
+        assert_eq!(
+            Day7::part2_as(TEST_INPUT, Arith::Checked, Budget::new()),
+            "40"
+        );
+        assert_eq!(Day7::part2_as(TEST_INPUT, Arith::Wide, Budget::new()), "40");
+    }
+
+    #[test]
+    fn tevents() {
+        let mut events = vec![];
+        Day7::part2_with(TEST_INPUT, (), &mut events, Budget::new());
+        assert_eq!(events.len(), 16);
+        assert_eq!(
+            events[2],
+            Event::Beams {
+                row: 2,
+                beams: vec![(6, 1), (8, 1)]
+            }
+        );
+
+        let Event::Beams { row, beams } = &events[15] else {
+            panic!("unexpected {:?}", events[15]);
+        };
+        assert_eq!(*row, 15);
+        assert_eq!(beams.iter().map(|b| b.1).sum::<i128>(), 40);
     }
 }
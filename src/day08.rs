@@ -1,43 +1,89 @@
-use std::{collections::HashSet, fmt::Debug};
+use std::{cmp::Reverse, collections::HashSet, fmt::Debug};
 
-use crate::Day;
+use crate::{
+    Arith, Budget, Day, Event, Int, Observer,
+    num::with_int,
+    parse::{Mode, ParseError, Span, lines, list, normalize},
+};
 
 pub struct Day8 {}
 
+#[derive(Debug, Clone, Copy)]
+pub struct Params {
+    /// Closest pairs to join in part 1; the examples use 10.
+    pub connections: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { connections: 1000 }
+    }
+}
+
 impl Day for Day8 {
-    fn part1(input: &str) -> impl crate::Answer {
-        solve1(input, 1000)
+    type Params = Params;
+
+    const ANSWERS: [&str; 2] = ["26400", "8199963486"];
+    const ARITH: [bool; 2] = [true, true];
+
+    fn part1_with(
+        input: &str,
+        params: Params,
+        obs: impl Observer,
+        _: Budget,
+    ) -> impl crate::Answer {
+        solve::<isize>(input, Until::Joined(params.connections), obs)
     }
 
-    fn part2(input: &str) -> impl crate::Answer {
-        solve1(input, -1)
+    fn part2_with(input: &str, _: Params, obs: impl Observer, _: Budget) -> impl crate::Answer {
+        solve::<isize>(input, Until::Connected, obs)
     }
+
+    fn part1_as(input: &str, arith: Arith, _: Budget) -> String {
+        let until = Until::Joined(Params::default().connections);
+        with_int!(arith, isize, solve(input, until, ()))
+    }
+
+    fn part2_as(input: &str, arith: Arith, _: Budget) -> String {
+        with_int!(arith, isize, solve(input, Until::Connected, ()))
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Until {
+    /// Stop after this many pairs and multiply the three biggest circuits.
+    Joined(usize),
+    /// Keep going until there's one circuit, and multiply the last pair's xs.
+    Connected,
 }
 
-fn solve1(input: &str, rounds: isize) -> isize {
-    let vs = parse(input);
+fn solve<N: Int>(input: &str, until: Until, mut obs: impl Observer) -> N {
+    let vs = parse(input.as_bytes()).expect("bad input");
     let mut pair_dists = vs
         .iter()
         .enumerate()
         .flat_map(|(idx, v)| {
             vs[idx + 1..].iter().cloned().map(|w| {
-                let dist = v.dist_sq(&w);
+                let dist = v.dist_sq::<N>(&w);
                 (dist, v.clone(), w)
             })
         })
-        .filter(|(d, _a, _b)| *d > 0)
-        .collect::<Vec<(Int, V3, V3)>>();
+        .filter(|(d, _a, _b)| *d > N::ZERO)
+        .collect::<Vec<(N, V3, V3)>>();
 
     pair_dists.sort_by_cached_key(|v| v.0);
     let mut circuits: Vec<HashSet<V3>> = vec![];
 
     for (round, (_, a, b)) in pair_dists.into_iter().enumerate() {
-        if rounds - (round as isize) == 0 {
+        if let Until::Joined(n) = until
+            && round == n
+        {
             break;
         }
 
         let a_loc = circuits.iter().position(|s| s.contains(&a));
         let b_loc = circuits.iter().position(|s| s.contains(&b));
+        let merged = a_loc.is_none() || a_loc != b_loc;
 
         if let Some(a_loc) = a_loc
             && let Some(b_loc) = b_loc
@@ -57,25 +103,40 @@ fn solve1(input: &str, rounds: isize) -> isize {
             circuits.push(new);
         }
 
+        if merged && obs.enabled() {
+            obs.event(Event::Merged {
+                a: [a.x, a.y, a.z],
+                b: [b.x, b.y, b.z],
+                circuits: circuits.len(),
+            });
+        }
+
         // exit condition - everything is connected
-        if circuits.len() == 1 && circuits[0].len() == vs.len() {
-            return a.x * b.x;
+        if let Until::Connected = until
+            && circuits.len() == 1
+            && circuits[0].len() == vs.len()
+        {
+            return N::from_isize(a.x) * N::from_isize(b.x);
         }
     }
 
     // find our 3 biggest circuits and * them
-    let mut circuit_sizes: Vec<isize> = circuits.into_iter().map(|c| -(c.len() as isize)).collect();
-    circuit_sizes.sort();
+    let mut circuit_sizes: Vec<usize> = circuits.into_iter().map(|c| c.len()).collect();
+    circuit_sizes.sort_by_key(|&s| Reverse(s));
 
-    -circuit_sizes.into_iter().take(3).product::<isize>()
+    circuit_sizes
+        .into_iter()
+        .take(3)
+        .map(N::from_usize)
+        .product()
 }
 
-type Int = isize;
+/// A junction box's position.
 #[derive(Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
-struct V3 {
-    x: Int,
-    y: Int,
-    z: Int,
+pub struct V3 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
 }
 
 impl Debug for V3 {
@@ -84,34 +145,30 @@ impl Debug for V3 {
     }
 }
 
-impl From<(Int, Int, Int)> for V3 {
-    fn from((x, y, z): (Int, Int, Int)) -> V3 {
+impl From<(isize, isize, isize)> for V3 {
+    fn from((x, y, z): (isize, isize, isize)) -> V3 {
         V3 { x, y, z }
     }
 }
 
 impl V3 {
-    fn dist_sq(&self, other: &Self) -> Int {
-        let dx = self.x - other.x;
-        let dy = self.y - other.y;
-        let dz = self.z - other.z;
+    fn dist_sq<N: Int>(&self, other: &Self) -> N {
+        let d = |a: isize, b: isize| N::from_isize(a) - N::from_isize(b);
+        let dx = d(self.x, other.x);
+        let dy = d(self.y, other.y);
+        let dz = d(self.z, other.z);
 
         dx * dx + dy * dy + dz * dz
     }
 }
 
-fn parse(input: &str) -> Vec<V3> {
-    input
-        .trim()
-        .lines()
-        .map(|l| {
-            let nums = l
-                .trim()
-                .split(",")
-                .map(|el| el.parse::<Int>().expect("bad num"))
-                .collect::<Vec<Int>>();
-
-            V3::from((nums[0], nums[1], nums[2]))
+/// The junction boxes, one `x,y,z` per line.
+pub fn parse(input: &[u8]) -> Result<Vec<V3>, ParseError> {
+    let input = normalize(input, Mode::TEXT);
+    lines(Span::new(&input))
+        .map(|l| match list(l, b',')?[..] {
+            [x, y, z] => Ok(V3::from((x, y, z))),
+            _ => Err(l.error("expected x,y,z")),
         })
         .collect()
 }
@@ -120,7 +177,7 @@ fn parse(input: &str) -> Vec<V3> {
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &'static str = "
+    const TEST_INPUT: &str = "
         162,817,812
         57,618,57
         906,360,560
@@ -145,18 +202,58 @@ mod tests {
 
     #[test]
     fn tparse() {
-        let r = parse(TEST_INPUT);
+        let r = parse(TEST_INPUT.as_bytes()).unwrap();
         assert_eq!(r.len(), 20);
         assert_eq!(*r.last().unwrap(), V3::from((425, 690, 689)));
     }
 
     #[test]
     fn tsolve1() {
-        assert_eq!(solve1(TEST_INPUT, 10), 40);
+        let params = Params { connections: 10 };
+        assert_eq!(
+            Day8::part1_with(TEST_INPUT, params, (), Budget::new()).to_string(),
+            "40"
+        );
     }
 
     #[test]
     fn tsolve2() {
-        assert_eq!(solve1(TEST_INPUT, -1), 25272);
+        assert_eq!(Day8::part2(TEST_INPUT).to_string(), "25272");
+    }
+
+    #[test]
+    fn tarith() {
+        let expect = Day8::part2(TEST_INPUT).to_string();
+        assert_eq!(
+            Day8::part2_as(TEST_INPUT, Arith::Checked, Budget::new()),
+            expect
+        );
+        assert_eq!(
+            Day8::part2_as(TEST_INPUT, Arith::Wide, Budget::new()),
+            expect
+        );
+        assert_eq!(solve::<i32>(TEST_INPUT, Until::Joined(10), ()), 40);
+    }
+
+    #[test]
+    fn tevents() {
+        let mut events = vec![];
+        Day8::part1_with(
+            TEST_INPUT,
+            Params { connections: 10 },
+            &mut events,
+            Budget::new(),
+        );
+
+        // one of the ten closest pairs was already in the same circuit
+        assert_eq!(events.len(), 9);
+        assert_eq!(
+            events[0],
+            Event::Merged {
+                a: [162, 817, 812],
+                b: [425, 690, 689],
+                circuits: 1
+            }
+        );
     }
 }
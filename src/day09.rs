@@ -1,73 +1,92 @@
-use std::cmp::minmax;
+use std::cmp::Reverse;
 
-use crate::{Day, intmap::Coord};
+use crate::{
+    Arith, Budget, Day, Int, Observer,
+    intmap::Coord,
+    num::with_int,
+    parse::{Mode, ParseError, Span, lines, list, normalize},
+    util::minmax,
+};
 
 pub struct Day9 {}
 
 impl Day for Day9 {
-    fn part1(input: &str) -> impl crate::Answer {
-        let vs = parse(input);
-        all_pairs(&vs).map(rect_size).max().unwrap()
+    type Params = ();
+    const ANSWERS: [&str; 2] = ["4777409595", "1473551379"];
+    const ARITH: [bool; 2] = [true, true];
+
+    fn part1_with(input: &str, _: (), _: impl Observer, _: Budget) -> impl crate::Answer {
+        solve1::<isize>(input)
+    }
+
+    fn part2_with(input: &str, _: (), _: impl Observer, _: Budget) -> impl crate::Answer {
+        solve2::<isize>(input)
+    }
+
+    fn part1_as(input: &str, arith: Arith, _: Budget) -> String {
+        with_int!(arith, isize, solve1(input))
     }
 
-    fn part2(input: &str) -> impl crate::Answer {
-        let mut vs = parse(input);
+    fn part2_as(input: &str, arith: Arith, _: Budget) -> String {
+        with_int!(arith, isize, solve2(input))
+    }
+}
 
-        let mut candidates = all_pairs(&vs)
-            .map(|p| (rect_size(p), p.0, p.1))
-            .collect::<Vec<_>>();
+fn solve1<N: Int>(input: &str) -> N {
+    let vs = parse(input.as_bytes()).expect("bad input");
+    all_pairs(&vs).map(rect_size).max().unwrap()
+}
 
-        candidates.sort_by_cached_key(|t| -t.0);
+fn solve2<N: Int>(input: &str) -> N {
+    let mut vs = parse(input.as_bytes()).expect("bad input");
 
-        // make it loop
-        vs.push(vs[0]);
+    let mut candidates = all_pairs(&vs)
+        .map(|p| (rect_size::<N>(p), p.0, p.1))
+        .collect::<Vec<_>>();
 
-        // let mut m = make_map(&vs);
-        let segments: Vec<[Coord<Int>; 2]> = vs
-            .windows(2)
-            .map(|w| [w[0], w[1]] as [Coord<Int>; 2])
-            .collect();
+    candidates.sort_by_key(|t| Reverse(t.0));
 
-        // dbg!(&segments);
+    // make it loop
+    vs.push(vs[0]);
 
-        candidates
-            .into_iter()
-            .filter(|(_, a, b)| valid_rect(&segments, a, b))
-            .map(|(d, _, _)| d)
-            .take(1)
-            .sum::<isize>()
-    }
+    // let mut m = make_map(&vs);
+    let segments: Vec<[Coord<isize>; 2]> = vs
+        .windows(2)
+        .map(|w| [w[0], w[1]] as [Coord<isize>; 2])
+        .collect();
+
+    candidates
+        .into_iter()
+        .filter(|(_, a, b)| valid_rect(&segments, a, b))
+        .map(|(d, _, _)| d)
+        .take(1)
+        .sum()
 }
 
-type Int = isize;
-fn parse(input: &str) -> Vec<Coord<Int>> {
-    input
-        .trim()
-        .lines()
-        .map(|l| {
-            let nums: Vec<Int> = l
-                .trim()
-                .split(",")
-                .map(|n| n.parse::<Int>().unwrap())
-                .collect();
-
-            Coord::from((nums[0], nums[1]))
+/// The red tiles, one `x,y` per line, in the order the loop visits them.
+pub fn parse(input: &[u8]) -> Result<Vec<Coord<isize>>, ParseError> {
+    let input = normalize(input, Mode::TEXT);
+    lines(Span::new(&input))
+        .map(|l| match list(l, b',')?[..] {
+            [x, y] => Ok(Coord::from((x, y))),
+            _ => Err(l.error("expected x,y")),
         })
         .collect()
 }
 
-fn all_pairs(vs: &[Coord<Int>]) -> impl Iterator<Item = (Coord<Int>, Coord<Int>)> {
+fn all_pairs(vs: &[Coord<isize>]) -> impl Iterator<Item = (Coord<isize>, Coord<isize>)> {
     vs.iter()
         .enumerate()
         .flat_map(|(idx, a)| vs[(idx + 1)..].iter().map(|b| (*a, *b)))
 }
 
-fn rect_size(pair: (Coord<Int>, Coord<Int>)) -> Int {
+fn rect_size<N: Int>(pair: (Coord<isize>, Coord<isize>)) -> N {
     let (a, b) = pair;
-    ((a.x - b.x).abs() + 1) * ((a.y - b.y).abs() + 1)
+    let side = |l: isize, r: isize| (N::from_isize(l) - N::from_isize(r)).abs() + N::ONE;
+    side(a.x, b.x) * side(a.y, b.y)
 }
 
-fn valid_rect(lines: &[[Coord<Int>; 2]], a: &Coord<Int>, b: &Coord<Int>) -> bool {
+fn valid_rect(lines: &[[Coord<isize>; 2]], a: &Coord<isize>, b: &Coord<isize>) -> bool {
     let [xmin, xmax] = minmax(a.x, b.x);
     let [ymin, ymax] = minmax(a.y, b.y);
 
@@ -104,8 +123,9 @@ fn valid_rect(lines: &[[Coord<Int>; 2]], a: &Coord<Int>, b: &Coord<Int>) -> bool
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Checked;
 
-    const TEST_INPUT: &'static str = "
+    const TEST_INPUT: &str = "
         7,1
         11,1
         11,7
@@ -118,7 +138,7 @@ mod tests {
 
     #[test]
     fn tparse() {
-        let r = parse(TEST_INPUT);
+        let r = parse(TEST_INPUT.as_bytes()).unwrap();
         assert_eq!(r.len(), 8);
         assert_eq!(r[0], Coord::from((7, 1)));
         assert_eq!(r[7], Coord::from((7, 3)));
@@ -126,7 +146,10 @@ mod tests {
 
     #[test]
     fn trect() {
-        assert_eq!(rect_size((Coord::from((2, 5)), Coord::from((9, 7)))), 24);
+        assert_eq!(
+            rect_size::<isize>((Coord::from((2, 5)), Coord::from((9, 7)))),
+            24
+        );
     }
 
     #[test]
@@ -136,18 +159,29 @@ mod tests {
 
     #[test]
     fn vrect() {
-        assert_eq!(
-            valid_rect(
-                &[[Coord::from((9, 7)), Coord::from((9, 5))]],
-                &Coord::from((7, 1)),
-                &Coord::from((11, 7))
-            ),
-            false
-        );
+        assert!(!valid_rect(
+            &[[Coord::from((9, 7)), Coord::from((9, 5))]],
+            &Coord::from((7, 1)),
+            &Coord::from((11, 7))
+        ));
     }
 
     #[test]
     fn p2() {
         assert_eq!(Day9::part2(TEST_INPUT).to_string(), "24");
     }
+
+    #[test]
+    fn arith() {
+        assert_eq!(
+            Day9::part1_as(TEST_INPUT, Arith::Checked, Budget::new()),
+            "50"
+        );
+        assert_eq!(Day9::part2_as(TEST_INPUT, Arith::Wide, Budget::new()), "24");
+
+        // 100_000 x 100_000 tiles don't fit in an i32
+        let far = "0,0\n99999,99999";
+        assert_eq!(solve1::<i64>(far), 10_000_000_000);
+        assert!(std::panic::catch_unwind(|| solve1::<Checked<i32>>(far)).is_err());
+    }
 }
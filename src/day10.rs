@@ -1,107 +1,301 @@
-use std::collections::HashSet;
-
-use crate::Day;
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    io::ErrorKind,
+    mem,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Budget, Day, Event, Observer,
+    cache::write_atomic,
+    manifest::sha256_hex,
+    parse::{Mode, ParseError, Span, groups, lines, list, normalize},
+};
 
 pub struct Day10 {}
 
-impl Day for Day10 {
-    fn part1(input: &str) -> impl crate::Answer {
-        let ms = parse(input);
+#[derive(Debug, Clone, Default)]
+pub struct Params {
+    /// Keep progress here so a run that crashed or ran out of budget picks
+    /// up where it stopped; machines already solved aren't searched again.
+    pub checkpoint: Option<PathBuf>,
+}
 
-        ms.iter().map(solve_machine_toggle).sum::<usize>()
+impl Day for Day10 {
+    type Params = Params;
+    const PARTS: u8 = 1;
+    const ANSWERS: [&str; 2] = ["417", "👻"];
+
+    fn part1_with(
+        input: &str,
+        params: Params,
+        obs: impl Observer,
+        budget: Budget,
+    ) -> impl crate::Answer {
+        solve(input, 1, press_toggle, params, obs, budget)
     }
 
-    fn part2(input: &str) -> impl crate::Answer {
+    #[allow(unreachable_code, unused_variables, unused_mut)]
+    fn part2_with(
+        input: &str,
+        params: Params,
+        mut obs: impl Observer,
+        budget: Budget,
+    ) -> impl crate::Answer {
         return 0;
 
-        let ms = parse(input);
-
-        ms.iter().map(solve_machine_inc).sum::<usize>()
+        solve(input, 2, press_inc, params, obs, budget)
     }
 }
 
-fn solve_machine_toggle(m: &Machine) -> usize {
-    let mut stacks = vec![MachineState {
-        steps: 0,
-        indicators: m.indicators.clone(),
-    }];
+/// How often a checkpoint is written while a machine's search goes on, even
+/// partway through a depth.
+const SAVE_EVERY: Duration = Duration::from_secs(10);
+
+/// Fewest presses summed over every machine. Machines the budget cut short
+/// count for nothing.
+fn solve(
+    input: &str,
+    part: u8,
+    press: Press,
+    params: Params,
+    mut obs: impl Observer,
+    budget: Budget,
+) -> usize {
+    let ms = parse(input.as_bytes()).expect("bad input");
+    let mut ck = Checkpoint::load(params.checkpoint, input, part);
+    let mut saved = Instant::now();
+
+    for (i, m) in ms.iter().enumerate() {
+        if ck.done.contains_key(&i) {
+            continue;
+        }
 
-    let mut seen_states = HashSet::new();
-    loop {
-        let mut new_stacks = vec![];
-        for stack in stacks {
-            for combo in &m.button_combos {
-                if seen_states.contains(&(stack.indicators.clone(), combo)) {
-                    continue;
+        loop {
+            let search = match &mut ck.searching {
+                Some((j, search)) if *j == i => search,
+                slot => &mut slot.insert((i, Search::start(m))).1,
+            };
+
+            let due = || saved.elapsed() >= SAVE_EVERY;
+            match search.layer(i, m, press, &mut obs, &budget, due) {
+                Layer::Found(n) => {
+                    ck.done.insert(i, n);
+                    ck.searching = None;
+                    ck.save();
+                    saved = Instant::now();
+                    break;
                 }
-                seen_states.insert((stack.indicators.clone(), combo));
-                let mut stack = stack.clone();
-                apply_combo_toggle(&mut stack.indicators, combo);
-                // stack.indicators ^= combo;
-                stack.steps += 1;
-
-                if stack.indicators == m.target_indicators {
-                    return dbg!(stack.steps);
+                Layer::Stopped => {
+                    ck.save();
+                    return ck.done.values().sum();
                 }
-
-                new_stacks.push(stack);
+                Layer::Save => {
+                    ck.save();
+                    saved = Instant::now();
+                }
+                Layer::Next => {}
+                Layer::Exhausted => panic!("machine {i} can't reach its target"),
             }
         }
+    }
 
-        stacks = new_stacks
+    ck.finish();
+    ck.done.values().sum()
+}
+
+/// What pressing a button did to a machine's state.
+enum Pressed {
+    Done,
+    Queue,
+    /// Can't lead anywhere.
+    Drop,
+}
+
+type Press = fn(&Machine, &mut [usize], &[usize]) -> Pressed;
+
+fn press_toggle(m: &Machine, indicators: &mut [usize], combo: &[usize]) -> Pressed {
+    apply_combo_toggle(indicators, combo);
+    if indicators == m.target_indicators {
+        Pressed::Done
+    } else {
+        Pressed::Queue
     }
 }
 
-fn solve_machine_inc(m: &Machine) -> usize {
-    let mut stacks = vec![MachineState {
-        steps: 0,
-        indicators: m.indicators.clone(),
-    }];
+fn press_inc(m: &Machine, joltages: &mut [usize], combo: &[usize]) -> Pressed {
+    for &i in combo {
+        joltages[i] += 1;
+        if joltages[i] > m.joltages[i] {
+            return Pressed::Drop;
+        }
+    }
 
-    let mut seen_states = HashSet::new();
-    loop {
-        let mut new_stacks = vec![];
-        for stack in stacks {
-            // dbg!(&stack);
-            for combo in &m.button_combos {
-                if seen_states.contains(&(stack.indicators.clone(), combo)) {
-                    continue;
-                }
-                seen_states.insert((stack.indicators.clone(), combo));
-                let mut stack = stack.clone();
-                stack.steps += 1;
-                let mut matched = true;
-                let mut overflowed = false;
-                for &i in combo {
-                    stack.indicators[i] += 1;
-                    if stack.indicators[i] > m.joltages[i] {
-                        overflowed = true;
-                        break;
-                    }
-                }
-                if overflowed {
+    if joltages == m.joltages {
+        Pressed::Done
+    } else {
+        Pressed::Queue
+    }
+}
+
+/// Where a machine's breadth-first search is at: states still to expand at
+/// this depth, those queued for the next, and the (state, button) pairs
+/// already tried.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Search {
+    todo: Vec<MachineState>,
+    next: Vec<MachineState>,
+    seen: HashSet<(Vec<usize>, usize)>,
+}
+
+enum Layer {
+    /// Fewest presses.
+    Found(usize),
+    Stopped,
+    /// Paused between states because `due` said it's time to save.
+    Save,
+    /// Moved on to the next depth.
+    Next,
+    /// Nothing left to try: the target can't be reached.
+    Exhausted,
+}
+
+impl Search {
+    fn start(m: &Machine) -> Search {
+        Search {
+            todo: vec![MachineState {
+                steps: 0,
+                indicators: m.indicators.clone(),
+            }],
+            next: vec![],
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Expands what's left of the current depth, asking `due` before each
+    /// state whether to pause for a save. Stopping or pausing partway leaves
+    /// `self` ready to carry on from the same state.
+    fn layer(
+        &mut self,
+        machine: usize,
+        m: &Machine,
+        press: Press,
+        obs: &mut impl Observer,
+        budget: &Budget,
+        mut due: impl FnMut() -> bool,
+    ) -> Layer {
+        while !self.todo.is_empty() {
+            if due() {
+                return Layer::Save;
+            }
+            let state = self.todo.pop().unwrap();
+            if !budget.step() {
+                self.todo.push(state);
+                return Layer::Stopped;
+            }
+            for (c, combo) in m.button_combos.iter().enumerate() {
+                if !self.seen.insert((state.indicators.clone(), c)) {
                     continue;
                 }
-                for i in 0..m.joltages.len() {
-                    if stack.indicators[i] != m.joltages[i] {
-                        matched = false;
-                    }
-                    if stack.indicators[i] > m.joltages[i] {
-                        overflowed = true;
-                        break;
-                    }
-                }
-                if matched {
-                    return stack.steps;
-                }
-                if overflowed {
-                    continue;
+                let mut state = state.clone();
+                state.steps += 1;
+
+                match press(m, &mut state.indicators, combo) {
+                    Pressed::Done => return Layer::Found(state.steps),
+                    Pressed::Queue => self.next.push(state),
+                    Pressed::Drop => {}
                 }
-                new_stacks.push(stack);
             }
         }
 
-        stacks = new_stacks
+        if self.next.is_empty() {
+            return Layer::Exhausted;
+        }
+        frontier(obs, machine, &self.next);
+        self.todo = mem::take(&mut self.next);
+        Layer::Next
+    }
+}
+
+/// Progress on one part for one input, as saved to `Params::checkpoint`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Checkpoint {
+    /// sha256 of the input; a checkpoint for any other input is ignored.
+    input: String,
+    part: u8,
+    /// Machine -> fewest presses.
+    done: BTreeMap<usize, usize>,
+    searching: Option<(usize, Search)>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl Checkpoint {
+    fn load(path: Option<PathBuf>, input: &str, part: u8) -> Checkpoint {
+        let input = sha256_hex(input);
+        let saved = path.as_ref().and_then(|p| match fs::read(p) {
+            Ok(json) => serde_json::from_slice::<Checkpoint>(&json)
+                .inspect_err(|e| eprintln!("warning: ignoring checkpoint {}: {e}", p.display()))
+                .ok(),
+            Err(e) if e.kind() == ErrorKind::NotFound => None,
+            Err(e) => {
+                eprintln!("warning: can't read checkpoint {}: {e}", p.display());
+                None
+            }
+        });
+
+        match saved {
+            Some(ck) if ck.input == input && ck.part == part => Checkpoint { path, ..ck },
+            _ => Checkpoint {
+                input,
+                part,
+                path,
+                ..Checkpoint::default()
+            },
+        }
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let saved = serde_json::to_vec(self)
+            .map_err(anyhow::Error::from)
+            .and_then(|json| {
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                Ok(write_atomic(path, &json)?)
+            });
+        if let Err(e) = saved {
+            eprintln!("warning: can't write checkpoint {}: {e:#}", path.display());
+        }
+    }
+
+    /// Every machine is solved, so there's nothing left to resume.
+    fn finish(&self) {
+        if let Some(path) = &self.path
+            && let Err(e) = fs::remove_file(path)
+            && e.kind() != ErrorKind::NotFound
+        {
+            eprintln!("warning: can't remove checkpoint {}: {e}", path.display());
+        }
+    }
+}
+
+fn frontier(obs: &mut impl Observer, machine: usize, stacks: &[MachineState]) {
+    if obs.enabled()
+        && let Some(s) = stacks.first()
+    {
+        obs.event(Event::Frontier {
+            machine,
+            depth: s.steps,
+            size: stacks.len(),
+        });
     }
 }
 
@@ -111,105 +305,106 @@ fn apply_combo_toggle(indicators: &mut [usize], indexes: &[usize]) {
     }
 }
 
+#[allow(dead_code)]
 fn apply_combo_inc(indicators: &mut [usize], indexes: &[usize]) {
     for &i in indexes {
         indicators[i] += 1;
     }
 }
 
-///- Types and utils
-
-#[derive(Debug, Clone)]
-struct Machine {
-    target_indicators: Vec<usize>,
-    indicators: Vec<usize>,
-    button_combos: Vec<Vec<usize>>,
-    joltages: Vec<usize>,
+//- Types and utils
+
+/// One line of the manual. Indicator lights are 0 for off and 1 for on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Machine {
+    /// The `[.##.]` diagram.
+    pub target_indicators: Vec<usize>,
+    /// All off, as the machine starts.
+    pub indicators: Vec<usize>,
+    /// The lights or counters each button `(1,3)` affects.
+    pub button_combos: Vec<Vec<usize>>,
+    /// The `{3,5,4,7}` requirements.
+    pub joltages: Vec<usize>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct MachineState {
     steps: usize,
     indicators: Vec<usize>,
 }
 
-fn parse(input: &str) -> Vec<Machine> {
-    input
-        .trim()
-        .lines()
+/// The machines, one per line like `[.##.] (3) (1,3) {3,5,4,7}`.
+pub fn parse(input: &[u8]) -> Result<Vec<Machine>, ParseError> {
+    let input = normalize(input, Mode::TEXT);
+    lines(Span::new(&input))
         .map(|l| {
-            let pieces = l.trim().split(" ");
             let mut target_indicators = vec![];
             let mut button_combos = vec![];
             let mut joltages = vec![];
 
-            for piece in pieces {
-                match &piece.as_bytes()[0..1] {
-                    b"[" => {
-                        target_indicators = parse_targets(piece);
-                    }
-                    b"(" => {
-                        button_combos.push(parse_num_list(&['(', ')'], piece));
-                    }
-                    b"{" => {
-                        joltages = parse_num_list(&['{', '}'], piece)
-                            .iter()
-                            .map(|j| *j as usize)
-                            .collect();
-                    }
-                    _ => {
-                        dbg!(piece);
-                        panic!("unexpected input")
-                    }
+            for (open, inner) in groups(l)? {
+                match open {
+                    b'[' => target_indicators = parse_targets(inner)?,
+                    b'(' => button_combos.push(list(inner, b',')?),
+                    b'{' => joltages = list(inner, b',')?,
+                    _ => return Err(inner.error(format!("unexpected {:?} group", open as char))),
                 }
             }
 
-            let mut indicators = vec![];
-            indicators.resize(target_indicators.len(), 0);
-            Machine {
+            let indicators = vec![0; target_indicators.len()];
+            Ok(Machine {
                 target_indicators,
                 indicators,
                 button_combos,
                 joltages,
-            }
+            })
         })
         .collect()
 }
 
-fn parse_targets(s: &str) -> Vec<usize> {
-    s.trim_matches(&['[', ']'])
-        .chars()
-        .map(|c| match c {
-            '.' => 0,
-            '#' => 1,
-            _ => {
-                dbg!(c);
-                panic!("bad char")
-            }
+fn parse_targets(s: Span) -> Result<Vec<usize>, ParseError> {
+    s.iter()
+        .enumerate()
+        .map(|(i, &c)| match c {
+            b'.' => Ok(0),
+            b'#' => Ok(1),
+            _ => Err(s.error_at(i, format!("bad char {:?}", c as char))),
         })
-        .collect::<Vec<_>>()
-}
-
-fn parse_num_list(strip: &[char; 2], s: &str) -> Vec<usize> {
-    s.trim_matches(strip)
-        .split(',')
-        .map(|n| n.parse().expect("should be a number"))
         .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::scratch::Scratch;
 
-    const TEST_INPUT: &'static str = "
+    const TEST_INPUT: &str = "
     [.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
     [...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
     [.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
     ";
 
+    /// One machine's search without a checkpoint; `None` if `budget` ran out first.
+    fn solve_machine(
+        machine: usize,
+        m: &Machine,
+        press: Press,
+        obs: &mut impl Observer,
+        budget: &Budget,
+    ) -> Option<usize> {
+        let mut search = Search::start(m);
+        loop {
+            match search.layer(machine, m, press, obs, budget, || false) {
+                Layer::Found(n) => return Some(n),
+                Layer::Stopped | Layer::Exhausted => return None,
+                Layer::Save | Layer::Next => {}
+            }
+        }
+    }
+
     #[test]
     fn tparse() {
-        let ms = parse(TEST_INPUT);
+        let ms = parse(TEST_INPUT.as_bytes()).unwrap();
         assert_eq!(ms.len(), 3);
 
         let m = &ms[1];
@@ -221,7 +416,7 @@ mod tests {
 
     #[test]
     fn tlogic() {
-        let mut ms = parse(TEST_INPUT);
+        let mut ms = parse(TEST_INPUT.as_bytes()).unwrap();
         let m = ms.get_mut(1).unwrap();
 
         apply_combo_toggle(&mut m.indicators, &m.button_combos[0]);
@@ -232,10 +427,40 @@ mod tests {
 
     #[test]
     fn tsolve_machine() {
-        let ms = parse(TEST_INPUT);
+        let ms = parse(TEST_INPUT.as_bytes()).unwrap();
         let m = &ms[1];
 
-        assert_eq!(solve_machine_toggle(m), 3);
+        assert_eq!(
+            solve_machine(1, m, press_toggle, &mut (), &Budget::new()),
+            Some(3)
+        );
+        assert_eq!(
+            solve_machine(0, &ms[0], press_inc, &mut (), &Budget::new()),
+            Some(10)
+        );
+
+        let mut events = vec![];
+        solve_machine(1, m, press_toggle, &mut events, &Budget::new());
+        let depths: Vec<_> = events
+            .iter()
+            .map(|e| match e {
+                Event::Frontier {
+                    machine: 1, depth, ..
+                } => *depth,
+                _ => panic!("unexpected {e:?}"),
+            })
+            .collect();
+        assert_eq!(depths, [1, 2]);
+    }
+
+    #[test]
+    fn tunreachable() {
+        // pressing (0) only ever toggles the first light
+        let ms = parse(b"[.#] (0) {0,1}").unwrap();
+        assert_eq!(
+            solve_machine(0, &ms[0], press_toggle, &mut (), &Budget::new()),
+            None
+        );
     }
 
     #[test]
@@ -243,6 +468,91 @@ mod tests {
         assert_eq!(Day10::part1(TEST_INPUT).to_string(), "7");
     }
 
+    #[test]
+    fn tbudget() {
+        let ms = parse(TEST_INPUT.as_bytes()).unwrap();
+        assert_eq!(
+            solve_machine(1, &ms[1], press_toggle, &mut (), &Budget::steps(3)),
+            None
+        );
+
+        // three states are enough for machine 0 but not for the others
+        let budget = Budget::steps(3);
+        let partial = Day10::part1_with(TEST_INPUT, Params::default(), (), budget.clone());
+        assert_eq!(partial.to_string(), "2");
+        assert!(budget.stopped());
+
+        let budget = Budget::new();
+        budget.cancel();
+        assert_eq!(
+            Day10::part1_with(TEST_INPUT, Params::default(), (), budget).to_string(),
+            "0"
+        );
+    }
+
+    #[test]
+    fn tsave_midway() {
+        let ms = parse(TEST_INPUT.as_bytes()).unwrap();
+        let m = &ms[1];
+        let layer = |s: &mut Search, due: &mut dyn FnMut() -> bool| {
+            s.layer(1, m, press_toggle, &mut (), &Budget::new(), due)
+        };
+        let indicators = |s: &Search| -> Vec<Vec<usize>> {
+            s.todo.iter().map(|st| st.indicators.clone()).collect()
+        };
+
+        let mut whole = Search::start(m);
+        assert!(matches!(layer(&mut whole, &mut || false), Layer::Next));
+        let mut paused = whole.clone();
+        assert!(matches!(layer(&mut whole, &mut || false), Layer::Next));
+
+        // due again once the second depth's first state is expanded
+        let mut checks = 0;
+        let mut due = || {
+            checks += 1;
+            checks > 1
+        };
+        assert!(matches!(layer(&mut paused, &mut due), Layer::Save));
+        assert!(!paused.todo.is_empty() && !paused.next.is_empty());
+
+        // what a checkpoint holds is enough to finish the depth the same way
+        let json = serde_json::to_vec(&paused).unwrap();
+        let mut resumed: Search = serde_json::from_slice(&json).unwrap();
+        assert!(matches!(layer(&mut resumed, &mut || false), Layer::Next));
+        assert_eq!(indicators(&resumed), indicators(&whole));
+    }
+
+    #[test]
+    fn tcheckpoint() {
+        // saving creates the directory too
+        let dir = Scratch::new("day10");
+        let path = dir.join("checkpoints/part1.json");
+        let params = || Params {
+            checkpoint: Some(path.clone()),
+        };
+
+        let partial = Day10::part1_with(TEST_INPUT, params(), (), Budget::steps(3));
+        assert_eq!(partial.to_string(), "2");
+
+        let ck = Checkpoint::load(Some(path.clone()), TEST_INPUT, 1);
+        assert_eq!(ck.done, BTreeMap::from([(0, 2)]));
+        assert!(matches!(ck.searching, Some((1, _))));
+        assert!(
+            Checkpoint::load(Some(path.clone()), TEST_INPUT, 2)
+                .done
+                .is_empty()
+        );
+
+        // machine 0 isn't searched again, and machine 1 carries on
+        let fresh = Budget::new();
+        Day10::part1_with(TEST_INPUT, Params::default(), (), fresh.clone());
+        let resumed = Budget::new();
+        let answer = Day10::part1_with(TEST_INPUT, params(), (), resumed.clone());
+        assert_eq!(answer.to_string(), "7");
+        assert_eq!(resumed.used() + 3, fresh.used());
+        assert!(!path.exists());
+    }
+
     #[test]
     fn tp2() {
         assert_eq!(Day10::part2(TEST_INPUT).to_string(), "33");
@@ -0,0 +1,451 @@
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::Context;
+
+use crate::{
+    cache::{compress_enabled, read_cached, write_cached},
+    config::{cache_dir, config, session},
+    manifest::{record_fetch, warn_if_changed},
+};
+#[cfg(feature = "fetch")]
+pub(crate) use net::{USER_AGENT, ensure_online, get_with, reserve_slot};
+
+pub const YEAR: u16 = 2025;
+pub const DAYS: u8 = 12;
+
+/// A lock older than this is assumed to belong to a process that died mid-fetch.
+const STALE_LOCK: Duration = Duration::from_secs(60);
+pub(crate) const LOCK_POLL: Duration = Duration::from_millis(100);
+
+/// A file we'd have to download, but we're `offline`.
+#[derive(Debug)]
+pub struct NotCached {
+    pub path: PathBuf,
+}
+
+impl Display for NotCached {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} isn't cached, and we're offline so it won't be fetched",
+            self.path.display()
+        )
+    }
+}
+
+impl std::error::Error for NotCached {}
+
+/// Whether to stay off the network entirely, so runs are the same on CI and
+/// air-gapped machines: always without the `fetch` feature or with `offline`,
+/// otherwise `AOC_OFFLINE` or the config's `offline`.
+pub fn offline() -> bool {
+    cfg!(feature = "offline")
+        || !cfg!(feature = "fetch")
+        || offline_var(env::var("AOC_OFFLINE").ok()).unwrap_or(config().offline)
+}
+
+fn offline_var(v: Option<String>) -> Option<bool> {
+    v.filter(|v| !v.is_empty())
+        .map(|v| !matches!(v.as_str(), "0" | "false" | "no"))
+}
+
+pub fn fetch_input(day: u8) -> Result<String, anyhow::Error> {
+    ensure_cache_dir()?;
+
+    let path = input_path(day);
+    let mut fetched = false;
+    let text = cached_or_fetch(&path, &lock_path(day), offline(), || {
+        fetched = true;
+        get(&input_url(day))
+    })?;
+
+    if !fetched {
+        warn_if_changed(day, &text);
+    } else if let Err(e) = record_fetch(day, &text) {
+        eprintln!("warning: couldn't record day {day} in the manifest: {e:#}");
+    }
+
+    Ok(text)
+}
+
+/// The puzzle description page, cached alongside the input.
+pub fn fetch_puzzle(day: u8) -> Result<String, anyhow::Error> {
+    ensure_cache_dir()?;
+    let path = cache_dir().join(format!("{day}.html"));
+    let lock = cache_dir().join(format!("{day}.html.lock"));
+    cached_or_fetch(&path, &lock, offline(), || get(&puzzle_url(day)))
+}
+
+/// Run `f` up to `attempts` times, doubling the pause between failures.
+pub fn retry<T>(
+    attempts: u32,
+    mut f: impl FnMut() -> Result<T, anyhow::Error>,
+) -> Result<T, anyhow::Error> {
+    let mut pause = Duration::from_secs(1);
+    for _ in 1..attempts {
+        match f() {
+            Ok(t) => return Ok(t),
+            Err(e) => eprintln!("{e:#}, retrying in {}s", pause.as_secs()),
+        }
+        thread::sleep(pause);
+        pause *= 2;
+    }
+
+    f()
+}
+
+pub fn fetch_input_s(day: &str) -> Result<String, anyhow::Error> {
+    fetch_input(day_number(day))
+}
+
+/// `"Day7"` / `"day07"` -> 7
+pub fn day_number(day: &str) -> u8 {
+    day.trim_matches(['d', 'a', 'y', 'D'])
+        .parse()
+        .expect("ends with a number")
+}
+
+/// Where `day`'s input is (or will be) cached.
+pub fn input_path(day: u8) -> PathBuf {
+    cache_dir().join(format!("{day}.txt"))
+}
+
+pub(crate) fn lock_path(day: u8) -> PathBuf {
+    cache_dir().join(format!("{day}.lock"))
+}
+
+pub(crate) fn ensure_cache_dir() -> Result<(), anyhow::Error> {
+    let dir = cache_dir();
+    fs::create_dir_all(&dir).with_context(|| format!("creating cache dir {}", dir.display()))
+}
+
+/// `AOC_BASE_URL` lets us point at a mirror or a local stub server.
+pub(crate) fn base_url() -> String {
+    env::var("AOC_BASE_URL")
+        .map(|u| u.trim_end_matches('/').to_string())
+        .unwrap_or("https://adventofcode.com".to_string())
+}
+
+pub(crate) fn puzzle_url(day: u8) -> String {
+    format!("{}/{YEAR}/day/{day}", base_url())
+}
+
+pub(crate) fn input_url(day: u8) -> String {
+    format!("{}/input", puzzle_url(day))
+}
+
+/// Authenticated GET through the shared client, spaced out by `MIN_INTERVAL`.
+/// Refused when `offline`.
+pub(crate) fn get(url: &str) -> Result<String, anyhow::Error> {
+    get_with(url, &session()?)
+}
+
+/// Without the `fetch` feature everything has to come from the cache.
+#[cfg(not(feature = "fetch"))]
+pub(crate) fn get_with(url: &str, _: &str) -> Result<String, anyhow::Error> {
+    anyhow::bail!("{url} isn't cached, and this build has no `fetch` feature to download it")
+}
+
+/// The parts that actually talk to the network.
+#[cfg(feature = "fetch")]
+mod net {
+    use std::{
+        fs,
+        path::Path,
+        sync::LazyLock,
+        thread,
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    };
+
+    use reqwest::{blocking::Client, header::COOKIE};
+
+    use super::{LockGuard, ensure_cache_dir};
+    use crate::{config::cache_dir, session::check_response};
+
+    pub(crate) const USER_AGENT: &str = concat!(
+        "github.com/mattdw/aoc25-rs/",
+        env!("CARGO_PKG_VERSION"),
+        " by mattdw"
+    );
+
+    /// Minimum gap between any two requests we make to the AoC servers.
+    pub(super) const MIN_INTERVAL: Duration = Duration::from_secs(2);
+
+    static CLIENT: LazyLock<Client> = LazyLock::new(|| {
+        Client::builder()
+            .user_agent(USER_AGENT)
+            .build()
+            .expect("could not build http client")
+    });
+
+    /// Every request goes out past this, blocking or async, so `offline` is
+    /// checked here rather than trusted to callers.
+    pub(crate) fn ensure_online(url: &str) -> Result<(), anyhow::Error> {
+        if super::offline() {
+            anyhow::bail!("offline, so not requesting {url}");
+        }
+        Ok(())
+    }
+
+    pub(crate) fn get_with(url: &str, session: &str) -> Result<String, anyhow::Error> {
+        ensure_online(url)?;
+        thread::sleep(reserve_slot()?);
+        let resp = CLIENT
+            .get(url)
+            .header(COOKIE, format!("session={session}"))
+            .send()?;
+        let status = resp.status();
+
+        check_response(url, status, resp.text()?)
+    }
+
+    /// Claim the next free request slot and return how long to wait until it.
+    ///
+    /// Slots are handed out `MIN_INTERVAL` apart, and the last one is kept in
+    /// the cache dir so every process, blocking or async, shares one rate limit.
+    pub(crate) fn reserve_slot() -> Result<Duration, anyhow::Error> {
+        ensure_cache_dir()?;
+        reserve_in(&cache_dir(), MIN_INTERVAL)
+    }
+
+    /// The slot after the one recorded in `dir`, at least `gap` later.
+    pub(super) fn reserve_in(dir: &Path, gap: Duration) -> Result<Duration, anyhow::Error> {
+        let _guard = LockGuard::acquire(&dir.join("throttle.lock"))?;
+        let path = dir.join("throttle");
+        let last = fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .map(|ms| UNIX_EPOCH + Duration::from_millis(ms));
+
+        let now = SystemTime::now();
+        let slot = match last {
+            Some(t) => (t + gap).max(now),
+            None => now,
+        };
+        fs::write(
+            &path,
+            slot.duration_since(UNIX_EPOCH)?.as_millis().to_string(),
+        )?;
+
+        Ok(slot.duration_since(now).unwrap_or_default())
+    }
+}
+
+/// Return the cached file at `path`, or take `lock` and fill it with `fetch`.
+/// When `offline` a miss is a `NotCached` error and `fetch` is never called.
+///
+/// If another process holds the lock we wait for it to finish and read what it
+/// wrote, rather than requesting the same thing twice.
+pub(crate) fn cached_or_fetch<F>(
+    path: &Path,
+    lock: &Path,
+    offline: bool,
+    fetch: F,
+) -> Result<String, anyhow::Error>
+where
+    F: FnOnce() -> Result<String, anyhow::Error>,
+{
+    if let Some(s) = read_cached(path) {
+        return Ok(s);
+    }
+    if offline {
+        return Err(NotCached {
+            path: path.to_owned(),
+        }
+        .into());
+    }
+
+    let _guard = loop {
+        if let Some(guard) = LockGuard::try_acquire(lock)? {
+            break guard;
+        }
+        thread::sleep(LOCK_POLL);
+        if let Some(s) = read_cached(path) {
+            return Ok(s);
+        }
+    };
+
+    // someone may have finished between our first read and taking the lock
+    if let Some(s) = read_cached(path) {
+        return Ok(s);
+    }
+
+    let text = fetch()?;
+    write_cached(path, &text, compress_enabled())?;
+
+    Ok(text)
+}
+
+pub(crate) struct LockGuard {
+    path: PathBuf,
+}
+
+impl LockGuard {
+    /// Wait for the lock, however long that takes.
+    pub(crate) fn acquire(path: &Path) -> std::io::Result<LockGuard> {
+        loop {
+            if let Some(guard) = LockGuard::try_acquire(path)? {
+                return Ok(guard);
+            }
+            thread::sleep(LOCK_POLL);
+        }
+    }
+
+    pub(crate) fn try_acquire(path: &Path) -> std::io::Result<Option<LockGuard>> {
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+        {
+            Ok(mut f) => {
+                let _ = write!(f, "{}", std::process::id());
+                Ok(Some(LockGuard {
+                    path: path.to_owned(),
+                }))
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                if is_stale(path) {
+                    let _ = fs::remove_file(path);
+                }
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl Drop for LockGuard {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn is_stale(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| SystemTime::now().duration_since(t).ok())
+        .is_some_and(|age| age > STALE_LOCK)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scratch::Scratch;
+
+    #[test]
+    fn tretry() {
+        let mut calls = 0;
+        let r = retry(3, || {
+            calls += 1;
+            if calls < 2 {
+                anyhow::bail!("not yet")
+            }
+            Ok(calls)
+        });
+        assert_eq!(r.unwrap(), 2);
+
+        let r: Result<(), _> = retry(1, || anyhow::bail!("never"));
+        assert!(r.is_err());
+    }
+
+    #[test]
+    fn toffline_var() {
+        assert_eq!(offline_var(None), None);
+        assert_eq!(offline_var(Some("".into())), None);
+        assert_eq!(offline_var(Some("1".into())), Some(true));
+        assert_eq!(offline_var(Some("yes".into())), Some(true));
+        assert_eq!(offline_var(Some("0".into())), Some(false));
+        assert_eq!(offline_var(Some("false".into())), Some(false));
+    }
+
+    #[test]
+    fn tnot_cached() {
+        let dir = Scratch::new("fetch-offline");
+        let path = dir.join("1.txt");
+        let lock = dir.join("1.lock");
+
+        let e =
+            cached_or_fetch(&path, &lock, true, || panic!("requested while offline")).unwrap_err();
+        assert!(e.downcast_ref::<NotCached>().is_some());
+        assert!(e.to_string().contains("1.txt isn't cached"));
+        assert!(!path.exists() && !lock.exists());
+
+        // what's already cached is still fine
+        write_cached(&path, "cached", false).unwrap();
+        let s = cached_or_fetch(&path, &lock, true, || panic!("requested while offline")).unwrap();
+        assert_eq!(s, "cached");
+    }
+
+    #[cfg(feature = "fetch")]
+    #[test]
+    fn tslots() {
+        // a dir of our own, as other tests share the cache dir's
+        let dir = Scratch::new("fetch-slots");
+        let gap = Duration::from_secs(2);
+        assert_eq!(net::reserve_in(&dir, gap).unwrap(), Duration::ZERO);
+
+        let second = net::reserve_in(&dir, gap).unwrap();
+        assert!(second > gap / 2 && second <= gap);
+        let third = net::reserve_in(&dir, gap).unwrap();
+        assert!(third > gap && third <= gap * 2);
+        assert!(!dir.join("throttle.lock").exists());
+    }
+
+    #[test]
+    fn tlock() {
+        let dir = Scratch::new("fetch-lock");
+        let lock = dir.join("1.lock");
+
+        let guard = LockGuard::try_acquire(&lock).unwrap();
+        assert!(guard.is_some());
+        assert!(LockGuard::try_acquire(&lock).unwrap().is_none());
+
+        drop(guard);
+        assert!(!lock.exists());
+        assert!(LockGuard::try_acquire(&lock).unwrap().is_some());
+    }
+
+    #[test]
+    fn tcached() {
+        let dir = Scratch::new("fetch-cached");
+        let path = dir.join("1.txt");
+        let lock = dir.join("1.lock");
+
+        let s = cached_or_fetch(&path, &lock, false, || Ok("fetched".to_string())).unwrap();
+        assert_eq!(s, "fetched");
+        assert!(!lock.exists());
+
+        let s = cached_or_fetch(&path, &lock, false, || panic!("should be cached")).unwrap();
+        assert_eq!(s, "fetched");
+    }
+
+    #[test]
+    fn twaits_for_other_fetcher() {
+        let dir = Scratch::new("fetch-wait");
+        let path = dir.join("1.txt");
+        let lock = dir.join("1.lock");
+
+        let held = LockGuard::try_acquire(&lock).unwrap().unwrap();
+        let writer = {
+            let path = path.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(300));
+                write_cached(&path, "from elsewhere", false).unwrap();
+                drop(held);
+            })
+        };
+
+        let s = cached_or_fetch(&path, &lock, false, || panic!("should wait")).unwrap();
+        assert_eq!(s, "from elsewhere");
+        writer.join().unwrap();
+    }
+}
@@ -0,0 +1,178 @@
+//! C ABI over the registered solvers, behind the `ffi` feature. Ordinary
+//! builds only make the rlib; the shared library is built on request:
+//!
+//! ```text
+//! cargo rustc --release --lib --crate-type cdylib --features ffi
+//! ```
+//!
+//! leaves it in `target/release/` (`libaoc25_rs.so` on Linux). Its header, `include/aoc25.h`, is generated from the functions
+//! and `AOC_*` codes below by `cargo run --bin gen_header`, which only
+//! translates a handful of types, so keep the signatures to those.
+//!
+//! Ownership: inputs stay the caller's and are only read during the call.
+//! Answers are allocated here and must go back through `aoc_free`, never
+//! the caller's own `free`.
+
+use std::{
+    ffi::{CString, c_char, c_int},
+    panic::catch_unwind,
+    ptr, slice,
+};
+
+use crate::{Arith, Budget, solver_for, solvers};
+
+/// Success.
+pub const AOC_OK: c_int = 0;
+/// No solver is registered for the day.
+pub const AOC_NO_DAY: c_int = 1;
+/// The part isn't 1 or 2, or the day doesn't really implement it.
+pub const AOC_NO_PART: c_int = 2;
+/// The input isn't UTF-8.
+pub const AOC_BAD_UTF8: c_int = 3;
+/// The solver panicked, usually on input it couldn't parse.
+pub const AOC_PANICKED: c_int = 4;
+/// A required pointer was null.
+pub const AOC_NULL: c_int = 5;
+
+/// Writes up to `cap` registered day numbers to `out` and returns how many
+/// there are in all, so a first call with `cap` 0 sizes the buffer.
+///
+/// # Safety
+///
+/// `out` must be valid for `cap` writes, or may be null if `cap` is 0.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_days(out: *mut u8, cap: usize) -> usize {
+    let days: Vec<u8> = solvers().iter().map(|s| s.day).collect();
+    if !out.is_null() {
+        let n = days.len().min(cap);
+        // SAFETY: the caller promises room for `cap` >= `n` bytes
+        unsafe { ptr::copy_nonoverlapping(days.as_ptr(), out, n) };
+    }
+    days.len()
+}
+
+/// Parts `day` really implements, or 0 if there's no solver for it.
+#[unsafe(no_mangle)]
+pub extern "C" fn aoc_parts(day: u8) -> u8 {
+    solver_for(day).map_or(0, |s| s.parts)
+}
+
+/// Solves `part` of `day` for the `len` bytes of UTF-8 at `input`. On
+/// `AOC_OK`, `*answer` is a NUL-terminated string to release with
+/// `aoc_free`; on any other code it's set to null.
+///
+/// # Safety
+///
+/// `input` must be valid for `len` reads and `answer` valid for one write.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_run(
+    day: u8,
+    part: u8,
+    input: *const u8,
+    len: usize,
+    answer: *mut *mut c_char,
+) -> c_int {
+    if answer.is_null() {
+        return AOC_NULL;
+    }
+    // SAFETY: checked non-null; the caller promises it's writable
+    unsafe { *answer = ptr::null_mut() };
+    if input.is_null() {
+        return AOC_NULL;
+    }
+
+    // SAFETY: the caller promises `len` readable bytes
+    let input = unsafe { slice::from_raw_parts(input, len) };
+    match run(day, part, input) {
+        Ok(s) => {
+            // SAFETY: as above
+            unsafe { *answer = s.into_raw() };
+            AOC_OK
+        }
+        Err(code) => code,
+    }
+}
+
+fn run(day: u8, part: u8, input: &[u8]) -> Result<CString, c_int> {
+    let solver = solver_for(day).ok_or(AOC_NO_DAY)?;
+    let f = match part {
+        1 | 2 if part > solver.parts => return Err(AOC_NO_PART),
+        1 => solver.part1,
+        2 => solver.part2,
+        _ => return Err(AOC_NO_PART),
+    };
+    let input = std::str::from_utf8(input).map_err(|_| AOC_BAD_UTF8)?;
+
+    // unwinding across the C boundary would abort the caller
+    let answer =
+        catch_unwind(|| f(input, Arith::Native, Budget::new())).map_err(|_| AOC_PANICKED)?;
+    // answers are numbers; an interior NUL would be a bug in a solver
+    CString::new(answer).map_err(|_| AOC_PANICKED)
+}
+
+/// Releases an answer from `aoc_run`. Null is ignored.
+///
+/// # Safety
+///
+/// `answer` must come from `aoc_run` and not have been freed already.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_free(answer: *mut c_char) {
+    if !answer.is_null() {
+        // SAFETY: the caller promises it's ours and still live
+        drop(unsafe { CString::from_raw(answer) });
+    }
+}
+
+// the tests lean on days 1 and 10
+#[cfg(all(test, feature = "all-days"))]
+mod tests {
+    use std::ffi::CStr;
+
+    use super::*;
+
+    fn call(day: u8, part: u8, input: &str) -> (c_int, Option<String>) {
+        let mut answer = ptr::null_mut();
+        let code = unsafe { aoc_run(day, part, input.as_ptr(), input.len(), &mut answer) };
+        if answer.is_null() {
+            return (code, None);
+        }
+
+        let s = unsafe { CStr::from_ptr(answer) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        unsafe { aoc_free(answer) };
+        (code, Some(s))
+    }
+
+    #[test]
+    fn tdays() {
+        let n = unsafe { aoc_days(ptr::null_mut(), 0) };
+        let mut days = vec![0; n];
+        assert_eq!(unsafe { aoc_days(days.as_mut_ptr(), 3) }, n);
+        assert_eq!(days[..4], [1, 2, 3, 0]);
+        assert_eq!((aoc_parts(10), aoc_parts(25)), (1, 0));
+    }
+
+    #[test]
+    fn trun() {
+        let input = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+        assert_eq!(call(1, 2, input), (AOC_OK, Some("6".into())));
+        assert_eq!(call(25, 1, input), (AOC_NO_DAY, None));
+        assert_eq!(call(10, 2, input), (AOC_NO_PART, None));
+        assert_eq!(call(1, 3, input), (AOC_NO_PART, None));
+        assert_eq!(call(1, 1, "L68\nX"), (AOC_PANICKED, None));
+
+        let mut answer = ptr::null_mut();
+        let bad = [b'L', 0xff];
+        assert_eq!(
+            unsafe { aoc_run(1, 1, bad.as_ptr(), 2, &mut answer) },
+            AOC_BAD_UTF8
+        );
+        assert_eq!(
+            unsafe { aoc_run(1, 1, ptr::null(), 0, &mut answer) },
+            AOC_NULL
+        );
+        unsafe { aoc_free(ptr::null_mut()) };
+    }
+}
@@ -18,29 +18,6 @@ impl<T: Default + Clone + std::fmt::Debug + PartialEq + Eq> IntMap<T> {
         }
     }
 
-    pub fn from_raw<F: Fn(char) -> T>(input: &str, mapper: F) -> IntMap<T> {
-        let width = input
-            .trim()
-            .replace('\r', "")
-            .find('\n')
-            .expect("must be row-wrapped with newlines");
-        let data: Vec<T> = input
-            .trim()
-            .replace(['\n', '\r', ' ', '\t'], "")
-            .chars()
-            .map(mapper)
-            .collect();
-
-        let height = data.len() / width;
-
-        assert_eq!(data.len(), width * height);
-        IntMap {
-            cells: data,
-            width,
-            height,
-        }
-    }
-
     pub fn in_bounds(&self, co: (isize, isize)) -> bool {
         (0..self.width).contains(&(co.0 as usize)) && (0..self.height).contains(&(co.1 as usize))
     }
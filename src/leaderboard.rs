@@ -0,0 +1,219 @@
+use std::{collections::BTreeMap, fmt::Write, path::Path, time::Duration};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::{
+    fetch::{YEAR, base_url, get},
+    unlock::{format_countdown, unlock_time},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    pub members: BTreeMap<u64, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    #[serde(default)]
+    pub last_star_ts: u64,
+    /// day -> part -> star
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u8, BTreeMap<u8, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Star {
+    pub get_star_ts: u64,
+}
+
+pub fn fetch_leaderboard(id: u64) -> Result<Leaderboard, anyhow::Error> {
+    let url = format!("{}/{YEAR}/leaderboard/private/view/{id}.json", base_url());
+    Leaderboard::parse(&get(&url)?)
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Leaderboard, anyhow::Error> {
+        serde_json::from_str(json).context("not a leaderboard JSON document")
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Leaderboard, anyhow::Error> {
+        let path = path.as_ref();
+        let json =
+            std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        Leaderboard::parse(&json)
+    }
+
+    pub fn year(&self) -> u16 {
+        self.event.parse().unwrap_or(YEAR)
+    }
+
+    /// Members ordered as AoC shows them: score, then stars, then earliest last star.
+    pub fn standings(&self) -> Vec<&Member> {
+        let mut ms: Vec<&Member> = self.members.values().collect();
+        ms.sort_by_key(|m| {
+            (
+                std::cmp::Reverse(m.local_score),
+                std::cmp::Reverse(m.stars),
+                m.last_star_ts,
+                m.id,
+            )
+        });
+        ms
+    }
+
+    fn days(&self) -> Vec<u8> {
+        let mut days: Vec<u8> = self
+            .members
+            .values()
+            .flat_map(|m| m.completion_day_level.keys().copied())
+            .collect();
+        days.sort();
+        days.dedup();
+        days
+    }
+
+    pub fn report(&self) -> String {
+        let year = self.year();
+        let standings = self.standings();
+        let width = standings
+            .iter()
+            .map(|m| m.display_name().len())
+            .max()
+            .unwrap_or(0);
+
+        let mut out = String::new();
+        for (rank, m) in standings.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "{:>2}) {:<width$}  {:>4} pts  {:>2}*",
+                rank + 1,
+                m.display_name(),
+                m.local_score,
+                m.stars,
+            );
+        }
+
+        for day in self.days() {
+            let _ = writeln!(out, "\nday {day}");
+            for m in standings
+                .iter()
+                .filter(|m| m.completion_day_level.contains_key(&day))
+            {
+                let time = |part| {
+                    m.solve_time(year, day, part)
+                        .map(format_countdown)
+                        .unwrap_or("-".to_string())
+                };
+                let _ = write!(
+                    out,
+                    "  {:<width$}  p1 {:>8}  p2 {:>8}",
+                    m.display_name(),
+                    time(1),
+                    time(2),
+                );
+                if let Some(delta) = m.part_delta(day) {
+                    let _ = write!(out, "  (+{})", format_countdown(delta));
+                }
+                out.push('\n');
+            }
+        }
+
+        out
+    }
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(n) => n.clone(),
+            None => format!("(anonymous #{})", self.id),
+        }
+    }
+
+    pub fn star_ts(&self, day: u8, part: u8) -> Option<u64> {
+        self.completion_day_level
+            .get(&day)?
+            .get(&part)
+            .map(|s| s.get_star_ts)
+    }
+
+    /// Time from the puzzle unlocking to this member getting the star.
+    pub fn solve_time(&self, year: u16, day: u8, part: u8) -> Option<Duration> {
+        let opened = unlock_time(year, day)
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()?
+            .as_secs();
+        let ts = self.star_ts(day, part)?;
+
+        Some(Duration::from_secs(ts.saturating_sub(opened)))
+    }
+
+    /// How long part 2 took after part 1.
+    pub fn part_delta(&self, day: u8) -> Option<Duration> {
+        let p1 = self.star_ts(day, 1)?;
+        let p2 = self.star_ts(day, 2)?;
+
+        Some(Duration::from_secs(p2.saturating_sub(p1)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../tests/fixtures/leaderboard.json");
+
+    #[test]
+    fn tparse() {
+        let lb = Leaderboard::parse(FIXTURE).unwrap();
+        assert_eq!(lb.year(), 2025);
+        assert_eq!(lb.members.len(), 3);
+        assert_eq!(lb.members[&1002].star_ts(2, 2), Some(1764653460));
+        assert_eq!(lb.members[&1002].star_ts(1, 2), None);
+        assert_eq!(lb.members[&1003].display_name(), "(anonymous #1003)");
+    }
+
+    #[test]
+    fn tload() {
+        let lb = Leaderboard::load("tests/fixtures/leaderboard.json").unwrap();
+        assert_eq!(lb.owner_id, 1001);
+        assert!(Leaderboard::parse("<html>").is_err());
+    }
+
+    #[test]
+    fn tstandings() {
+        let lb = Leaderboard::parse(FIXTURE).unwrap();
+        let ids: Vec<u64> = lb.standings().iter().map(|m| m.id).collect();
+        assert_eq!(ids, [1001, 1002, 1003]);
+    }
+
+    #[test]
+    fn ttimes() {
+        let lb = Leaderboard::parse(FIXTURE).unwrap();
+        let alice = &lb.members[&1001];
+        assert_eq!(alice.solve_time(2025, 1, 1), Some(Duration::from_secs(600)));
+        assert_eq!(
+            alice.solve_time(2025, 2, 2),
+            Some(Duration::from_secs(7200))
+        );
+        assert_eq!(alice.part_delta(2), Some(Duration::from_secs(3600)));
+        assert_eq!(lb.members[&1002].part_delta(1), None);
+    }
+
+    #[test]
+    fn treport() {
+        let report = Leaderboard::parse(FIXTURE).unwrap().report();
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines[0], " 1) alice                10 pts   4*");
+        assert_eq!(lines[2], " 3) (anonymous #1003)     0 pts   0*");
+        assert!(lines.contains(&"  bob                p1 00:05:00  p2        -"));
+        assert!(lines.contains(&"  bob                p1 00:30:00  p2 00:31:00  (+00:01:00)"));
+    }
+}
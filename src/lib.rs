@@ -1,81 +1,164 @@
-#![feature(get_disjoint_mut_helpers)]
-#![feature(cmp_minmax)]
-#![feature(iter_map_windows)]
+use std::fmt::Display;
 
-use reqwest::header::COOKIE;
-use std::{env, fmt::Display};
+#[cfg(feature = "fetch")]
+mod calendar;
+#[cfg(feature = "fetch")]
+pub use calendar::{Progress, fetch_stars, parse_calendar, progress, status_grid};
 
-mod intmap;
+mod budget;
+pub use budget::Budget;
+
+mod cache;
+pub use cache::{compress_cache, compress_enabled};
 
-mod day01;
-pub use day01::Day1;
+mod config;
+pub use config::{
+    Config, Profile, active_profile, cache_dir, config, expected_answer, set_profile,
+};
 
-mod day02;
-pub use day02::Day2;
+#[cfg(feature = "ffi")]
+mod ffi;
 
-mod day03;
-pub use day03::Day3;
+mod fetch;
+pub use fetch::{
+    DAYS, NotCached, YEAR, day_number, fetch_input, fetch_input_s, fetch_puzzle, input_path,
+    offline, retry,
+};
 
-mod day04;
-pub use day04::Day4;
+mod manifest;
+pub use manifest::{Entry, Integrity, Manifest, record_verified, verify_all, verify_input};
 
-mod day05;
-pub use day05::Day5;
+mod leaderboard;
+pub use leaderboard::{Leaderboard, Member, fetch_leaderboard};
 
-mod day06;
-pub use day06::Day6;
+mod num;
+pub use num::{Arith, Checked, Int};
 
-mod day07;
-pub use day07::Day7;
+mod observe;
+pub use observe::{Event, Observer, Trace};
 
-mod day08;
-pub use day08::Day8;
+#[cfg(feature = "fetch")]
+mod prefetch;
+#[cfg(feature = "fetch")]
+pub use prefetch::{Prefetched, prefetch};
 
-mod day09;
-pub use day09::Day9;
+mod registry;
+pub use registry::{Solver, solver_for, solvers};
 
-mod day10;
-pub use day10::Day10;
+mod session;
+pub use session::{LoggedOut, SessionStatus, check_session};
 
-// mod day11;
-// pub use day11::Day11;
+mod unlock;
+pub use unlock::{format_countdown, is_unlocked, unlock_time, wait_for_unlock};
 
-// mod day12;
-// pub use day12::Day12;
+mod intmap;
+pub use intmap::{Coord, CoordIterator, IntMap};
+
+mod parse;
+pub use parse::ParseError;
+
+mod util;
+
+#[cfg(test)]
+mod scratch;
+
+#[cfg(feature = "day01")]
+pub mod day01;
+#[cfg(feature = "day02")]
+pub mod day02;
+#[cfg(feature = "day03")]
+pub mod day03;
+#[cfg(feature = "day04")]
+pub mod day04;
+#[cfg(feature = "day05")]
+pub mod day05;
+#[cfg(feature = "day06")]
+pub mod day06;
+#[cfg(feature = "day07")]
+pub mod day07;
+#[cfg(feature = "day08")]
+pub mod day08;
+#[cfg(feature = "day09")]
+pub mod day09;
+#[cfg(feature = "day10")]
+pub mod day10;
+
+/// Registers each day declared above: re-exports its struct, adds it to
+/// `registry::solvers`, and lists it in `for_each_day!` for the integration
+/// tests. `build.rs` writes the invocation from the `dayNN.rs` files in `src/`
+/// whose feature is on.
+///
+/// The leading `$` is handed back in so the generated macro can have its own
+/// metavariables.
+macro_rules! days {
+    ($d:tt $($day:literal => $module:ident::$name:ident;)*) => {
+        $(pub use $module::$name;)*
+
+        fn registered() -> Vec<registry::Solver> {
+            vec![$(registry::solver::<$name>($day),)*]
+        }
+
+        /// Invokes `$m!(module, Struct)` for every registered day.
+        #[macro_export]
+        macro_rules! for_each_day {
+            ($d m:ident) => {
+                $( $d m!($module, $name); )*
+            };
+        }
+    };
+}
+
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
 
 pub trait Answer: Eq + Display + std::fmt::Debug {}
 impl<T: Eq + Display + std::fmt::Debug> Answer for T {}
 
 pub trait Day {
-    fn part1(input: &str) -> impl Answer;
-    fn part2(input: &str) -> impl Answer;
-}
-
-pub fn fetch_input(day: u8) -> Result<String, anyhow::Error> {
-    let _ = std::fs::create_dir("inputs");
-    let existing = std::fs::read_to_string(format!("inputs/{day}.txt"));
-
-    if let Ok(s) = existing {
-        return Ok(s);
+    /// Numbers the puzzle text fixes for the real input but sets differently
+    /// for its examples; `()` if there are none.
+    type Params: Default;
+
+    /// Parts with a real solution; a part that just returns a dummy answer
+    /// doesn't count.
+    const PARTS: u8 = 2;
+
+    /// Answers for the default profile's input, checked by `tests/all.rs`.
+    const ANSWERS: [&str; 2] = ["👻", "👻"];
+
+    /// Which parts' `partN_as` really use the `Arith` they're given, by
+    /// being generic over `Int`.
+    const ARITH: [bool; 2] = [false, false];
+
+    /// Long searches check `budget` as they go; if it runs out they return
+    /// what they have so far and `budget.stopped()` says so.
+    fn part1_with(
+        input: &str,
+        params: Self::Params,
+        obs: impl Observer,
+        budget: Budget,
+    ) -> impl Answer;
+    fn part2_with(
+        input: &str,
+        params: Self::Params,
+        obs: impl Observer,
+        budget: Budget,
+    ) -> impl Answer;
+
+    fn part1(input: &str) -> impl Answer {
+        Self::part1_with(input, Self::Params::default(), (), Budget::new())
     }
 
-    let session = env::var("SESSION").expect("SESSION env var is required to fetch input");
-    let client = reqwest::blocking::Client::new();
-    let resp = client
-        .get(format!("https://adventofcode.com/2025/day/{day}/input"))
-        .header(COOKIE, format!("session={session}"))
-        .send()?;
-
-    let text: String = resp.text()?;
-    std::fs::write(format!("inputs/{day}.txt"), &text)?;
+    fn part2(input: &str) -> impl Answer {
+        Self::part2_with(input, Self::Params::default(), (), Budget::new())
+    }
 
-    Ok(text)
-}
+    /// `part1` with the integers `arith` asks for, for days whose solvers
+    /// are generic over `Int`, see `ARITH`. Others ignore it.
+    fn part1_as(input: &str, _: Arith, budget: Budget) -> String {
+        Self::part1_with(input, Self::Params::default(), (), budget).to_string()
+    }
 
-pub fn fetch_input_s(day: &str) -> Result<String, anyhow::Error> {
-    let s: u8 = day
-        .trim_matches(['d', 'a', 'y', 'D'])
-        .parse()
-        .expect("ends with a number");
-    fetch_input(s)
+    fn part2_as(input: &str, _: Arith, budget: Budget) -> String {
+        Self::part2_with(input, Self::Params::default(), (), budget).to_string()
+    }
 }
@@ -0,0 +1,260 @@
+use std::{
+    env,
+    io::{Write, stdout},
+    panic::catch_unwind,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    thread,
+    time::Duration,
+};
+
+use anyhow::{Context, bail};
+use aoc25_rs::{
+    Arith, Budget, DAYS, Integrity, Leaderboard, Prefetched, SessionStatus, YEAR, active_profile,
+    check_session, compress_cache, fetch_input, fetch_leaderboard, fetch_puzzle, fetch_stars,
+    format_countdown, input_path, prefetch, progress, retry, set_profile, solver_for, status_grid,
+    verify_all, wait_for_unlock,
+};
+#[cfg(feature = "day10")]
+use aoc25_rs::{Day, Day10, day10};
+
+const USAGE: &str = "usage: aoc25-rs [--profile <name>] <command>
+
+    run <day> [--checked|--i128] [--timeout <secs>]
+                  solve <day>, panicking on overflow or with i128 throughout
+                  in the parts that support it; past the timeout, searches stop with what they have so far
+    run 10 --checkpoint <dir>
+                  save day 10's progress in <dir> and resume from it
+    prefetch      fetch every unlocked input that isn't cached yet
+    wait <day>    count down to <day> unlocking, then fetch it
+    leaderboard <id|file.json>
+                  private leaderboard standings and solve times
+    session check is SESSION still logged in?
+    where <day>   path of <day>'s cached input
+    verify        check cached inputs against the manifest
+    status        stars on the site against solvers in this repo
+    cache compress
+                  gzip every plain file in the input cache
+
+AOC_OFFLINE=1 never touches the network: anything not cached is an error.";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    if let ["--profile", name, ..] = args[..] {
+        if let Err(e) = set_profile(name) {
+            eprintln!("{e:#}");
+            return ExitCode::FAILURE;
+        }
+        args.drain(..2);
+    }
+
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[&str]) -> Result<(), anyhow::Error> {
+    match args {
+        ["run", day, flags @ ..] => cmd_run(parse_day(day)?, flags),
+        ["prefetch"] => cmd_prefetch(),
+        ["wait", day] => cmd_wait(parse_day(day)?),
+        ["session", "check"] => cmd_session_check(),
+        ["cache", "compress"] => {
+            println!("compressed {} files", compress_cache()?);
+            Ok(())
+        }
+        ["verify"] => cmd_verify(),
+        ["status"] => {
+            print!("{}", status_grid(&progress(&fetch_stars()?)));
+            Ok(())
+        }
+        ["where", day] => {
+            println!("{}", input_path(parse_day(day)?).display());
+            Ok(())
+        }
+        ["leaderboard", src] => cmd_leaderboard(src),
+        _ => bail!(USAGE),
+    }
+}
+
+fn cmd_run(day: u8, mut flags: &[&str]) -> Result<(), anyhow::Error> {
+    let mut arith = Arith::Native;
+    let mut timeout = None;
+    let mut checkpoints: Option<PathBuf> = None;
+    loop {
+        match flags {
+            ["--checked", rest @ ..] => (arith, flags) = (Arith::Checked, rest),
+            ["--i128", rest @ ..] => (arith, flags) = (Arith::Wide, rest),
+            ["--timeout", secs, rest @ ..] => {
+                let bad = || format!("bad timeout {secs:?}");
+                let secs: f64 = secs.parse().with_context(bad)?;
+                timeout = Some(Duration::try_from_secs_f64(secs).with_context(bad)?);
+                flags = rest;
+            }
+            // without day 10 built, `run 10` stops at the missing solver below
+            ["--checkpoint", dir, rest @ ..] if day == 10 => {
+                (checkpoints, flags) = (Some(PathBuf::from(dir)), rest)
+            }
+            ["--checkpoint", ..] => bail!("only day 10 can checkpoint so far"),
+            [] => break,
+            _ => bail!(USAGE),
+        }
+    }
+
+    let Some(solver) = solver_for(day) else {
+        bail!("no solver for day {day} yet");
+    };
+    let input = fetch_input(day)?;
+
+    let parts = [solver.part1, solver.part2];
+    for (n, part) in parts.iter().take(solver.parts as usize).enumerate() {
+        let budget = Budget::new();
+        if let Some(timeout) = timeout {
+            let budget = budget.clone();
+            // left sleeping if the part finishes first; it dies with the process
+            thread::spawn(move || {
+                thread::sleep(timeout);
+                budget.cancel();
+            });
+        }
+
+        if arith != Arith::Native && !solver.arith[n] {
+            println!(
+                "part {}: only runs with native integers, not {arith:?}",
+                n + 1
+            );
+            continue;
+        }
+
+        let run = || match &checkpoints {
+            Some(dir) => run_day10(n, &input, dir, budget.clone()),
+            None => part(&input, arith, budget.clone()),
+        };
+
+        // an overflow in one part shouldn't hide the other's answer
+        match catch_unwind(run) {
+            Ok(answer) if budget.stopped() => {
+                println!("part {}: {answer} (incomplete, timed out)", n + 1)
+            }
+            Ok(answer) => println!("part {}: {answer}", n + 1),
+            Err(_) => println!("part {}: panicked", n + 1),
+        }
+    }
+
+    Ok(())
+}
+
+/// Day 10 with its search checkpointed in `dir`, one file per part.
+#[cfg(feature = "day10")]
+fn run_day10(n: usize, input: &str, dir: &Path, budget: Budget) -> String {
+    let params = day10::Params {
+        checkpoint: Some(dir.join(format!("day10-part{}.json", n + 1))),
+    };
+    match n {
+        0 => Day10::part1_with(input, params, (), budget).to_string(),
+        _ => Day10::part2_with(input, params, (), budget).to_string(),
+    }
+}
+
+#[cfg(not(feature = "day10"))]
+fn run_day10(_: usize, _: &str, _: &Path, _: Budget) -> String {
+    unreachable!("there's no day 10 solver to get this far")
+}
+
+fn cmd_prefetch() -> Result<(), anyhow::Error> {
+    let results = prefetch()?;
+
+    let days = |want: fn(&Prefetched) -> bool| {
+        results
+            .iter()
+            .filter(|(_, p)| want(p))
+            .map(|(day, _)| day.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    println!("fetched: {}", days(|p| matches!(p, Prefetched::Fetched)));
+    println!("cached:  {}", days(|p| matches!(p, Prefetched::Cached)));
+    println!("locked:  {}", days(|p| matches!(p, Prefetched::Locked)));
+    for (day, p) in &results {
+        if let Prefetched::Failed(e) = p {
+            println!("failed:  day {day}: {e:#}");
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_wait(day: u8) -> Result<(), anyhow::Error> {
+    wait_for_unlock(YEAR, day, |left| {
+        print!("\rday {day} unlocks in {}  ", format_countdown(left));
+        let _ = stdout().flush();
+    });
+    println!("\rday {day} is open, fetching");
+
+    let input = retry(6, || fetch_input(day))?;
+    let puzzle = retry(6, || fetch_puzzle(day))?;
+    println!(
+        "fetched day {day}: {} byte input, {} byte puzzle",
+        input.len(),
+        puzzle.len()
+    );
+
+    Ok(())
+}
+
+fn cmd_leaderboard(src: &str) -> Result<(), anyhow::Error> {
+    let lb = match src.parse::<u64>() {
+        Ok(id) => fetch_leaderboard(id)?,
+        Err(_) => Leaderboard::load(src)?,
+    };
+    print!("{}", lb.report());
+
+    Ok(())
+}
+
+fn cmd_verify() -> Result<(), anyhow::Error> {
+    for (day, integrity) in verify_all()? {
+        match integrity {
+            Integrity::Ok => println!("day {day:>2}: ok"),
+            Integrity::Untracked => println!("day {day:>2}: new, now tracked"),
+            Integrity::Changed { expected, size } => println!(
+                "day {day:>2}: CHANGED since it was fetched ({size} bytes, was {})",
+                expected.size
+            ),
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_session_check() -> Result<(), anyhow::Error> {
+    match check_session()? {
+        SessionStatus::Valid { user } => println!("session ok, logged in as {user}"),
+        SessionStatus::Expired => bail!("session has expired, grab a fresh cookie into SESSION"),
+        SessionStatus::Missing => match active_profile() {
+            Some((name, _)) => bail!(
+                "profile {name} has no session; set it in the config or SESSION_{}",
+                name.to_uppercase()
+            ),
+            None => bail!("SESSION is not set"),
+        },
+    }
+
+    Ok(())
+}
+
+fn parse_day(s: &str) -> Result<u8, anyhow::Error> {
+    let day: u8 = s.parse().with_context(|| format!("bad day {s:?}"))?;
+    if !(1..=DAYS).contains(&day) {
+        bail!("day must be between 1 and {DAYS}");
+    }
+
+    Ok(day)
+}
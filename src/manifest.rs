@@ -0,0 +1,239 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    cache::{read_cached, write_atomic},
+    config::cache_dir,
+    fetch::{DAYS, LockGuard, input_path},
+};
+
+/// What we knew about each cached input when it was fetched, plus the answers
+/// last checked against it. Lives at `manifest.json` in the cache dir.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    pub inputs: BTreeMap<u8, Entry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub sha256: String,
+    pub size: usize,
+    /// Unix seconds.
+    pub fetched_at: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Integrity {
+    Ok,
+    /// Nothing recorded yet; the input has now been adopted as-is.
+    Untracked,
+    Changed {
+        expected: Entry,
+        size: usize,
+    },
+}
+
+impl Entry {
+    fn of(input: &str, fetched_at: u64) -> Entry {
+        Entry {
+            sha256: sha256_hex(input),
+            size: input.len(),
+            fetched_at,
+            part1: None,
+            part2: None,
+        }
+    }
+}
+
+pub(crate) fn sha256_hex(s: &str) -> String {
+    Sha256::digest(s.as_bytes())
+        .iter()
+        .fold(String::new(), |mut out, b| {
+            let _ = write!(out, "{b:02x}");
+            out
+        })
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl Manifest {
+    fn path(dir: &Path) -> PathBuf {
+        dir.join("manifest.json")
+    }
+
+    pub fn load(dir: &Path) -> Result<Manifest, anyhow::Error> {
+        match fs::read_to_string(Manifest::path(dir)) {
+            Ok(s) => Ok(serde_json::from_str(&s)?),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Manifest::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save(&self, dir: &Path) -> Result<(), anyhow::Error> {
+        let json = serde_json::to_string_pretty(self)?;
+        write_atomic(&Manifest::path(dir), json.as_bytes())?;
+        Ok(())
+    }
+
+    /// Load, change and save the manifest while holding its lock, since
+    /// parallel test processes all want to write to it.
+    fn update<T>(dir: &Path, f: impl FnOnce(&mut Manifest) -> T) -> Result<T, anyhow::Error> {
+        let _guard = LockGuard::acquire(&dir.join("manifest.lock"))?;
+        let mut m = Manifest::load(dir)?;
+        let out = f(&mut m);
+        m.save(dir)?;
+        Ok(out)
+    }
+}
+
+pub(crate) fn record_fetch(day: u8, input: &str) -> Result<(), anyhow::Error> {
+    record_fetch_in(&cache_dir(), day, input)
+}
+
+fn record_fetch_in(dir: &Path, day: u8, input: &str) -> Result<(), anyhow::Error> {
+    Manifest::update(dir, |m| {
+        m.inputs.insert(day, Entry::of(input, now()));
+    })
+}
+
+/// Compare a cached input against the manifest, adopting it if it's new to us.
+pub fn verify_input(day: u8, input: &str) -> Result<Integrity, anyhow::Error> {
+    verify_input_in(&cache_dir(), day, input)
+}
+
+fn verify_input_in(dir: &Path, day: u8, input: &str) -> Result<Integrity, anyhow::Error> {
+    let sha = sha256_hex(input);
+    if let Some(e) = Manifest::load(dir)?.inputs.get(&day) {
+        return Ok(if e.sha256 == sha {
+            Integrity::Ok
+        } else {
+            Integrity::Changed {
+                expected: e.clone(),
+                size: input.len(),
+            }
+        });
+    }
+
+    Manifest::update(dir, |m| {
+        m.inputs.entry(day).or_insert(Entry::of(input, now()));
+    })?;
+
+    Ok(Integrity::Untracked)
+}
+
+/// Note that `answer` was checked correct for `part` against the current input.
+pub fn record_verified(day: u8, part: u8, answer: &str) -> Result<(), anyhow::Error> {
+    record_verified_in(&cache_dir(), day, part, answer)
+}
+
+fn record_verified_in(dir: &Path, day: u8, part: u8, answer: &str) -> Result<(), anyhow::Error> {
+    Manifest::update(dir, |m| {
+        if let Some(e) = m.inputs.get_mut(&day) {
+            let slot = if part == 1 {
+                &mut e.part1
+            } else {
+                &mut e.part2
+            };
+            *slot = Some(answer.to_string());
+        }
+    })
+}
+
+/// Check every cached input against the manifest.
+pub fn verify_all() -> Result<Vec<(u8, Integrity)>, anyhow::Error> {
+    let mut out = vec![];
+    for day in 1..=DAYS {
+        if let Some(input) = read_cached(&input_path(day)) {
+            out.push((day, verify_input(day, &input)?));
+        }
+    }
+
+    Ok(out)
+}
+
+pub(crate) fn warn_if_changed(day: u8, input: &str) {
+    match verify_input(day, input) {
+        Ok(Integrity::Changed { expected, size }) => {
+            eprintln!(
+                "warning: cached input for day {day} doesn't match the manifest \
+                 ({size} bytes, expected {}); was it edited, truncated or swapped?",
+                expected.size
+            );
+            if expected.part1.is_some() || expected.part2.is_some() {
+                eprintln!(
+                    "         answers last verified against the recorded input: {} / {}",
+                    expected.part1.as_deref().unwrap_or("-"),
+                    expected.part2.as_deref().unwrap_or("-"),
+                );
+            }
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("warning: couldn't check manifest for day {day}: {e:#}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scratch::Scratch;
+
+    #[test]
+    fn tsha() {
+        assert_eq!(
+            sha256_hex("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn tverify() {
+        let dir = Scratch::new("manifest-verify");
+
+        assert_eq!(
+            verify_input_in(&dir, 1, "L68\n").unwrap(),
+            Integrity::Untracked
+        );
+        assert_eq!(verify_input_in(&dir, 1, "L68\n").unwrap(), Integrity::Ok);
+
+        let Integrity::Changed { expected, size } = verify_input_in(&dir, 1, "L6").unwrap() else {
+            panic!("truncated input should be flagged");
+        };
+        assert_eq!((expected.size, size), (4, 2));
+
+        record_fetch_in(&dir, 1, "L6").unwrap();
+        assert_eq!(verify_input_in(&dir, 1, "L6").unwrap(), Integrity::Ok);
+    }
+
+    #[test]
+    fn tanswers() {
+        let dir = Scratch::new("manifest-answers");
+        record_fetch_in(&dir, 3, "987654321111111\n").unwrap();
+        record_verified_in(&dir, 3, 2, "3121910778619").unwrap();
+
+        let m = Manifest::load(&dir).unwrap();
+        assert_eq!(m.inputs[&3].part1, None);
+        assert_eq!(m.inputs[&3].part2.as_deref(), Some("3121910778619"));
+        assert!(!dir.join("manifest.lock").exists());
+
+        let json = fs::read_to_string(dir.join("manifest.json")).unwrap();
+        assert!(json.contains("\"sha256\""));
+    }
+}
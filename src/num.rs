@@ -0,0 +1,276 @@
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Div, Mul, MulAssign, Rem, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// Which integers to run the generic solvers with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Arith {
+    /// Whatever the day picked, wrapping on overflow in release builds.
+    #[default]
+    Native,
+    /// The day's type, but panicking on overflow.
+    Checked,
+    /// `i128` throughout.
+    Wide,
+}
+
+/// What the numeric solvers need from their integer type.
+pub trait Int:
+    Copy
+    + Ord
+    + Hash
+    + Debug
+    + Display
+    + FromStr
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + Sum
+    + Product
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// Panics if `n` doesn't fit.
+    fn from_usize(n: usize) -> Self;
+    /// Panics if `n` doesn't fit.
+    fn from_isize(n: isize) -> Self;
+    /// Panics if `self` doesn't fit, which only a huge `u128` won't.
+    fn to_i128(self) -> i128;
+    fn abs(self) -> Self;
+    fn pow(self, exp: u32) -> Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_pow(self, exp: u32) -> Option<Self>;
+}
+
+macro_rules! int {
+    ($abs:ident: $($t:ty),*) => {$(
+        impl Int for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn from_usize(n: usize) -> Self {
+                <$t>::try_from(n).expect("doesn't fit")
+            }
+            fn from_isize(n: isize) -> Self {
+                <$t>::try_from(n).expect("doesn't fit")
+            }
+            fn to_i128(self) -> i128 {
+                i128::try_from(self).expect("doesn't fit")
+            }
+            fn abs(self) -> Self {
+                int!(@$abs self)
+            }
+            fn pow(self, exp: u32) -> Self {
+                <$t>::pow(self, exp)
+            }
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+            fn checked_pow(self, exp: u32) -> Option<Self> {
+                <$t>::checked_pow(self, exp)
+            }
+        }
+    )*};
+    (@signed $x:ident) => { $x.abs() };
+    (@unsigned $x:ident) => { $x };
+}
+
+int!(signed: i32, i64, i128, isize);
+int!(unsigned: u8, u32, u64, u128, usize);
+
+/// `T`, but overflowing panics instead of wrapping, whatever the build profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Checked<T>(pub T);
+
+impl<T: Display> Display for Checked<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: FromStr> FromStr for Checked<T> {
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Checked)
+    }
+}
+
+macro_rules! checked_op {
+    ($($op:ident::$f:ident / $assign:ident::$fa:ident => $checked:ident),*) => {$(
+        impl<T: Int> $op for Checked<T> {
+            type Output = Self;
+
+            fn $f(self, rhs: Self) -> Self {
+                match self.0.$checked(rhs.0) {
+                    Some(n) => Checked(n),
+                    None => panic!(
+                        "overflow: {:?} {} {:?}",
+                        self.0,
+                        stringify!($f),
+                        rhs.0
+                    ),
+                }
+            }
+        }
+
+        impl<T: Int> $assign for Checked<T> {
+            fn $fa(&mut self, rhs: Self) {
+                *self = self.$f(rhs);
+            }
+        }
+    )*};
+}
+
+checked_op!(
+    Add::add / AddAssign::add_assign => checked_add,
+    Sub::sub / SubAssign::sub_assign => checked_sub,
+    Mul::mul / MulAssign::mul_assign => checked_mul
+);
+
+// division can only overflow for MIN / -1, which the wrapped type already panics on
+impl<T: Int> Div for Checked<T> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        Checked(self.0 / rhs.0)
+    }
+}
+
+impl<T: Int> Rem for Checked<T> {
+    type Output = Self;
+
+    fn rem(self, rhs: Self) -> Self {
+        Checked(self.0 % rhs.0)
+    }
+}
+
+impl<T: Int> Sum for Checked<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |a, b| a + b)
+    }
+}
+
+impl<T: Int> Product for Checked<T> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |a, b| a * b)
+    }
+}
+
+impl<T: Int> Int for Checked<T> {
+    const ZERO: Self = Checked(T::ZERO);
+    const ONE: Self = Checked(T::ONE);
+
+    fn from_usize(n: usize) -> Self {
+        Checked(T::from_usize(n))
+    }
+    fn from_isize(n: isize) -> Self {
+        Checked(T::from_isize(n))
+    }
+    fn to_i128(self) -> i128 {
+        self.0.to_i128()
+    }
+    fn abs(self) -> Self {
+        Checked(self.0.abs())
+    }
+    fn pow(self, exp: u32) -> Self {
+        match self.0.checked_pow(exp) {
+            Some(n) => Checked(n),
+            None => panic!("overflow: {:?} pow {exp}", self.0),
+        }
+    }
+
+    fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Checked)
+    }
+    fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Checked)
+    }
+    fn checked_mul(self, rhs: Self) -> Option<Self> {
+        self.0.checked_mul(rhs.0).map(Checked)
+    }
+    fn checked_pow(self, exp: u32) -> Option<Self> {
+        self.0.checked_pow(exp).map(Checked)
+    }
+}
+
+/// `$f::<N>(args)` as a string, with `N` chosen by `$arith`: `$native`,
+/// `Checked<$native>` or `i128`.
+#[cfg_attr(not(feature = "all-days"), allow(unused_macros))]
+macro_rules! with_int {
+    ($arith:expr, $native:ty, $f:ident($($arg:expr),*)) => {
+        match $arith {
+            $crate::Arith::Native => $f::<$native>($($arg),*).to_string(),
+            $crate::Arith::Checked => $f::<$crate::Checked<$native>>($($arg),*).to_string(),
+            $crate::Arith::Wide => $f::<i128>($($arg),*).to_string(),
+        }
+    };
+}
+#[cfg_attr(not(feature = "all-days"), allow(unused_imports))]
+pub(crate) use with_int;
+
+#[cfg(test)]
+mod tests {
+    use std::panic::catch_unwind;
+
+    use super::*;
+
+    #[test]
+    fn tchecked() {
+        let a = Checked(i64::MAX / 2);
+        assert_eq!(a + a, Checked(i64::MAX - 1));
+        assert_eq!("-12".parse::<Checked<i64>>(), Ok(Checked(-12)));
+        assert_eq!(
+            [Checked(3u8), Checked(4)].into_iter().sum::<Checked<u8>>(),
+            Checked(7)
+        );
+        assert_eq!(Checked(-3).abs(), Checked(3));
+
+        assert!(catch_unwind(|| a * Checked(3)).is_err());
+        assert!(catch_unwind(|| Checked(0u64) - Checked(1)).is_err());
+        assert!(catch_unwind(|| Checked(10u32).pow(10)).is_err());
+        assert!(
+            catch_unwind(|| [Checked(200u8), Checked(100)]
+                .into_iter()
+                .sum::<Checked<u8>>())
+            .is_err()
+        );
+    }
+
+    fn sum_squares<N: Int>(xs: &[usize]) -> N {
+        xs.iter()
+            .map(|&x| N::from_usize(x) * N::from_usize(x))
+            .sum()
+    }
+
+    #[test]
+    fn twith_int() {
+        let big = [4_000_000_000];
+        assert_eq!(
+            with_int!(Arith::Wide, u32, sum_squares(&big)),
+            "16000000000000000000"
+        );
+        assert!(catch_unwind(|| with_int!(Arith::Checked, u64, sum_squares(&big))).is_ok());
+        assert!(catch_unwind(|| with_int!(Arith::Checked, i64, sum_squares(&big))).is_err());
+    }
+}
@@ -0,0 +1,96 @@
+use std::fmt::Display;
+
+/// Something a solver passed through on the way to its answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// Day 4: a round of removing reachable rolls finished; `grid` is the
+    /// map afterwards, one line per row.
+    Removed {
+        round: usize,
+        removed: usize,
+        grid: String,
+    },
+    /// Day 7: the beams leaving `row`, as `(column, timelines)` sorted by column.
+    Beams {
+        row: usize,
+        beams: Vec<(isize, i128)>,
+    },
+    /// Day 8: boxes `a` and `b` were joined, merging their circuits;
+    /// `circuits` counts those with more than one box.
+    Merged {
+        a: [isize; 3],
+        b: [isize; 3],
+        circuits: usize,
+    },
+    /// Day 10: states queued for the next step of `machine`'s search.
+    Frontier {
+        machine: usize,
+        depth: usize,
+        size: usize,
+    },
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Event::Removed {
+                round,
+                removed,
+                grid,
+            } => write!(f, "round {round}: removed {removed}\n{grid}"),
+            Event::Beams { row, beams } => write!(f, "row {row}: {beams:?}"),
+            Event::Merged { a, b, circuits } => {
+                write!(f, "joined {a:?} and {b:?}, {circuits} circuits")
+            }
+            Event::Frontier {
+                machine,
+                depth,
+                size,
+            } => write!(f, "machine {machine}: {size} states at depth {depth}"),
+        }
+    }
+}
+
+/// Receives a solver's events. Solvers check `enabled` before building one,
+/// so with `()`, the default, the hooks compile away.
+pub trait Observer {
+    fn enabled(&self) -> bool {
+        true
+    }
+
+    fn event(&mut self, e: Event);
+}
+
+impl Observer for () {
+    fn enabled(&self) -> bool {
+        false
+    }
+
+    fn event(&mut self, _: Event) {}
+}
+
+impl<O: Observer> Observer for &mut O {
+    fn enabled(&self) -> bool {
+        (**self).enabled()
+    }
+
+    fn event(&mut self, e: Event) {
+        (**self).event(e)
+    }
+}
+
+/// Collects every event, for tests.
+impl Observer for Vec<Event> {
+    fn event(&mut self, e: Event) {
+        self.push(e)
+    }
+}
+
+/// Prints every event to stderr.
+pub struct Trace;
+
+impl Observer for Trace {
+    fn event(&mut self, e: Event) {
+        eprintln!("{e}");
+    }
+}
@@ -0,0 +1,481 @@
+// with only some days built, some of these helpers go unused
+#![cfg_attr(not(feature = "all-days"), allow(dead_code, unused_imports))]
+
+use std::{
+    borrow::Cow,
+    fmt::{Debug, Display},
+    ops::{Deref, RangeInclusive},
+    str::FromStr,
+};
+
+use crate::intmap::IntMap;
+
+/// What went wrong and where, counting lines and columns from 1.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub col: usize,
+    pub msg: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.col, self.msg)
+    }
+}
+
+// so `.expect()` on a parse result says where it failed
+impl Debug for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// What `normalize` may change. Parsers pick the mode matching how much of
+/// the input's layout they depend on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Mode {
+    /// Drop leading blank lines and the indentation common to every line, so
+    /// indented example constants look like real inputs.
+    pub dedent: bool,
+    /// `\r\n` to `\n`.
+    pub crlf: bool,
+    /// Drop the trailing newline and any blank lines before it.
+    pub trim_end: bool,
+    /// Pad every line with spaces to the longest one's width.
+    pub pad: bool,
+}
+
+impl Mode {
+    /// Line-based input, or a grid: spaces inside lines are kept but the
+    /// layout around them isn't significant.
+    pub const TEXT: Mode = Mode {
+        dedent: true,
+        crlf: true,
+        trim_end: true,
+        pad: false,
+    };
+
+    /// Input read by column, where every line has to be the same width.
+    pub const COLUMNS: Mode = Mode {
+        pad: true,
+        ..Mode::TEXT
+    };
+}
+
+/// Clean `input` up as far as `mode` allows, copying it only if a line itself
+/// changes; dropping blank lines at either end just narrows the slice.
+pub(crate) fn normalize(input: &[u8], mode: Mode) -> Cow<'_, [u8]> {
+    let blank = |l: &[u8]| l.trim_ascii().is_empty();
+    let mut lines: Vec<&[u8]> = input.split(|&b| b == b'\n').collect();
+    let mut start = 0;
+    let mut changed = false;
+
+    if mode.crlf {
+        for l in &mut lines {
+            if let Some(stripped) = l.strip_suffix(b"\r") {
+                *l = stripped;
+                changed = true;
+            }
+        }
+    }
+    if mode.trim_end {
+        while lines.len() > 1 && lines.last().is_some_and(|l| blank(l)) {
+            lines.pop();
+        }
+    }
+    if mode.dedent {
+        let first = lines.iter().position(|l| !blank(l)).unwrap_or(0);
+        start = lines.drain(..first).map(|l| l.len() + 1).sum();
+
+        let indent = lines
+            .iter()
+            .filter(|l| !blank(l))
+            .map(|l| l.len() - l.trim_ascii_start().len())
+            .min()
+            .unwrap_or(0);
+        if indent > 0 {
+            for l in &mut lines {
+                *l = &l[indent.min(l.len())..];
+            }
+            changed = true;
+        }
+    }
+    let width = match mode.pad {
+        true => lines.iter().map(|l| l.len()).max().unwrap_or(0),
+        false => 0,
+    };
+    if lines.iter().any(|l| l.len() < width) {
+        changed = true;
+    }
+
+    if !changed {
+        let len = lines.iter().map(|l| l.len() + 1).sum::<usize>() - 1;
+        return Cow::Borrowed(&input[start..start + len]);
+    }
+
+    let mut out = Vec::with_capacity(input.len());
+    for (i, l) in lines.iter().enumerate() {
+        if i > 0 {
+            out.push(b'\n');
+        }
+        out.extend_from_slice(l);
+        out.resize(out.len() + width.saturating_sub(l.len()), b' ');
+    }
+
+    Cow::Owned(out)
+}
+
+/// A piece of the input that remembers where it came from. Derefs to its bytes.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Span<'a> {
+    pub bytes: &'a [u8],
+    pub line: usize,
+    pub col: usize,
+}
+
+impl<'a> Deref for Span<'a> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.bytes
+    }
+}
+
+impl Debug for Span<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{} {:?}",
+            self.line,
+            self.col,
+            String::from_utf8_lossy(self.bytes)
+        )
+    }
+}
+
+impl<'a> Span<'a> {
+    pub fn new(input: &'a [u8]) -> Span<'a> {
+        Span {
+            bytes: input,
+            line: 1,
+            col: 1,
+        }
+    }
+
+    /// Where `bytes[at]` is.
+    fn pos(&self, at: usize) -> (usize, usize) {
+        let prefix = &self.bytes[..at];
+        match prefix.iter().rposition(|&b| b == b'\n') {
+            Some(nl) => (
+                self.line + prefix.iter().filter(|&&b| b == b'\n').count(),
+                at - nl,
+            ),
+            None => (self.line, self.col + at),
+        }
+    }
+
+    pub fn slice(&self, start: usize, end: usize) -> Span<'a> {
+        let (line, col) = self.pos(start);
+        Span {
+            bytes: &self.bytes[start..end],
+            line,
+            col,
+        }
+    }
+
+    pub fn trim(&self) -> Span<'a> {
+        let start = self.len() - self.trim_ascii_start().len();
+        let end = start + self[start..].trim_ascii_end().len();
+        self.slice(start, end)
+    }
+
+    pub fn error(&self, msg: impl Display) -> ParseError {
+        ParseError {
+            line: self.line,
+            col: self.col,
+            msg: msg.to_string(),
+        }
+    }
+
+    /// The error for `bytes[at]`.
+    pub fn error_at(&self, at: usize, msg: impl Display) -> ParseError {
+        self.slice(at, at).error(msg)
+    }
+
+    pub fn text(&self) -> std::borrow::Cow<'a, str> {
+        String::from_utf8_lossy(self.bytes)
+    }
+
+    /// Pieces between each `sep`, untrimmed.
+    pub fn split(self, sep: u8) -> impl Iterator<Item = Span<'a>> {
+        let (mut line, mut col) = (self.line, self.col);
+        self.bytes.split(move |&b| b == sep).map(move |piece| {
+            let span = Span {
+                bytes: piece,
+                line,
+                col,
+            };
+            // step over the piece and its separator
+            for &b in piece.iter().chain([&sep]) {
+                if b == b'\n' {
+                    line += 1;
+                    col = 1;
+                } else {
+                    col += 1;
+                }
+            }
+            span
+        })
+    }
+}
+
+/// Lines of `input`, each trimmed of surrounding whitespace (including `\r`),
+/// with leading and trailing blank lines dropped.
+pub(crate) fn lines(input: Span) -> impl Iterator<Item = Span> {
+    let end = input.trim_ascii_end().len();
+    input
+        .slice(0, end)
+        .split(b'\n')
+        .map(|l| l.trim())
+        .skip_while(|l| l.is_empty())
+}
+
+/// Lines of `input` exactly as they are, bar a trailing `\r`, for inputs
+/// where whitespace is significant.
+pub(crate) fn raw_lines(input: Span) -> impl Iterator<Item = Span> {
+    input.split(b'\n').map(|l| match l.strip_suffix(b"\r") {
+        Some(_) => l.slice(0, l.len() - 1),
+        None => l,
+    })
+}
+
+/// Runs of non-blank lines.
+pub(crate) fn blocks(input: Span) -> Vec<Span> {
+    let mut out = vec![];
+    let mut start = None;
+    let mut at = 0;
+    for line in input.bytes.split(|&b| b == b'\n') {
+        match (line.trim_ascii().is_empty(), start) {
+            (false, None) => start = Some(at),
+            (true, Some(s)) => {
+                out.push(input.slice(s, at - 1).trim());
+                start = None;
+            }
+            _ => {}
+        }
+        at += line.len() + 1;
+    }
+    if let Some(s) = start {
+        out.push(input.slice(s, input.len()).trim());
+    }
+
+    out
+}
+
+pub(crate) fn int<T: FromStr>(s: Span) -> Result<T, ParseError> {
+    std::str::from_utf8(s.bytes)
+        .ok()
+        .and_then(|t| t.parse().ok())
+        .ok_or_else(|| s.error(format!("expected a number, found {:?}", s.text())))
+}
+
+/// One number per byte, e.g. `987654321`.
+pub(crate) fn digits(s: Span) -> Result<Vec<u8>, ParseError> {
+    s.iter()
+        .enumerate()
+        .map(|(i, &b)| match b {
+            b'0'..=b'9' => Ok(b - b'0'),
+            _ => Err(s.error_at(i, format!("expected a digit, found {:?}", b as char))),
+        })
+        .collect()
+}
+
+/// `sep`-separated numbers, with nothing else allowed between them.
+pub(crate) fn list<T: FromStr>(s: Span, sep: u8) -> Result<Vec<T>, ParseError> {
+    s.split(sep).map(|f| int(f.trim())).collect()
+}
+
+/// `a-b` as `a..=b`.
+pub(crate) fn range<T: FromStr>(s: Span) -> Result<RangeInclusive<T>, ParseError> {
+    // skip the first byte so `a` can be negative
+    let Some(at) = s.iter().skip(1).position(|&b| b == b'-') else {
+        return Err(s.error(format!("expected a-b, found {:?}", s.text())));
+    };
+
+    Ok(int(s.slice(0, at + 1).trim())?..=int(s.slice(at + 2, s.len()).trim())?)
+}
+
+/// Whitespace-separated bracketed groups like `[.##.] (1,3) {3,5}`, as the
+/// opening bracket and what's inside.
+pub(crate) fn groups(s: Span) -> Result<Vec<(u8, Span)>, ParseError> {
+    let mut out = vec![];
+    let mut i = 0;
+    while i < s.len() {
+        let open = s[i];
+        if open.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let close = match open {
+            b'(' => b')',
+            b'[' => b']',
+            b'{' => b'}',
+            b'<' => b'>',
+            _ => return Err(s.error_at(i, format!("expected a bracket, found {:?}", open as char))),
+        };
+        let Some(len) = s[i + 1..].iter().position(|&b| b == close) else {
+            return Err(s.error_at(i, format!("unclosed {:?}", open as char)));
+        };
+
+        out.push((open, s.slice(i + 1, i + 1 + len)));
+        i += len + 2;
+    }
+
+    Ok(out)
+}
+
+/// A rectangular map, one row per line, with `cell` turning each byte into
+/// a `T`, or `None` if it shouldn't be there. Rows are taken as they are, so
+/// `s` should be normalized first.
+pub(crate) fn grid<T>(s: Span, cell: impl Fn(u8) -> Option<T>) -> Result<IntMap<T>, ParseError> {
+    let mut cells = vec![];
+    let mut width = None;
+    let mut height = 0;
+    for row in raw_lines(s) {
+        if *width.get_or_insert(row.len()) != row.len() {
+            return Err(row.error(format!(
+                "row is {} wide, expected {}",
+                row.len(),
+                width.unwrap_or(0)
+            )));
+        }
+        for (i, &b) in row.iter().enumerate() {
+            match cell(b) {
+                Some(c) => cells.push(c),
+                None => return Err(row.error_at(i, format!("unexpected {:?}", b as char))),
+            }
+        }
+        height += 1;
+    }
+
+    Ok(IntMap {
+        cells,
+        width: width.unwrap_or(0),
+        height,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts<'a>(spans: impl IntoIterator<Item = Span<'a>>) -> Vec<String> {
+        spans.into_iter().map(|s| s.text().into_owned()).collect()
+    }
+
+    #[test]
+    fn tlines() {
+        let input = Span::new(b"\n   1,2\r\n   3,4\n\n   5\n");
+        let ls: Vec<Span> = lines(input).collect();
+        assert_eq!(texts(ls.clone()), ["1,2", "3,4", "", "5"]);
+        assert_eq!((ls[1].line, ls[1].col), (3, 4));
+
+        let raw = raw_lines(Span::new(b" 1 \r\n2  "));
+        assert_eq!(texts(raw), [" 1 ", "2  "]);
+    }
+
+    #[test]
+    fn tblocks() {
+        let input = Span::new(b"3-5\n10-14\n\n  \n1\n5\n");
+        let bs = blocks(input);
+        assert_eq!(texts(bs.clone()), ["3-5\n10-14", "1\n5"]);
+        assert_eq!(bs[1].line, 5);
+    }
+
+    #[test]
+    fn tnumbers() {
+        assert_eq!(list::<u8>(Span::new(b"3, 5,4"), b','), Ok(vec![3, 5, 4]));
+        assert_eq!(range::<u64>(Span::new(b"11-22")), Ok(11..=22));
+        assert_eq!(range::<i64>(Span::new(b"-3--1")), Ok(-3..=-1));
+
+        let e = list::<u8>(Span::new(b"1, 2, x"), b',').unwrap_err();
+        assert_eq!((e.line, e.col), (1, 7));
+        let e = list::<u8>(Span::new(b"1,300"), b',').unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 1, column 3: expected a number, found \"300\""
+        );
+        assert!(range::<u8>(Span::new(b"35")).is_err());
+
+        assert_eq!(digits(Span::new(b"9870")), Ok(vec![9, 8, 7, 0]));
+        assert_eq!(digits(Span::new(b"98-0")).unwrap_err().col, 3);
+    }
+
+    #[test]
+    fn tgroups() {
+        let gs = groups(Span::new(b"[.##.] (3) (1,3) {3,5}")).unwrap();
+        let opens: Vec<u8> = gs.iter().map(|g| g.0).collect();
+        assert_eq!(opens, b"[(({");
+        assert_eq!(texts(gs.iter().map(|g| g.1)), [".##.", "3", "1,3", "3,5"]);
+        assert_eq!(gs[2].1.col, 13);
+
+        let e = groups(Span::new(b"(1) 2")).unwrap_err();
+        assert_eq!(e.col, 5);
+        assert!(groups(Span::new(b"(1")).is_err());
+    }
+
+    #[test]
+    fn tnormalize() {
+        let real = b"123 328\n 45 64 \n*   +  \n";
+        let Cow::Borrowed(trimmed) = normalize(real, Mode::TEXT) else {
+            panic!("a real input shouldn't be copied");
+        };
+        assert_eq!(trimmed, &real[..real.len() - 1]);
+        let padded = b"\n\n.#\n#.\n\n";
+        assert!(matches!(
+            normalize(padded, Mode::TEXT),
+            Cow::Borrowed(b".#\n#.")
+        ));
+
+        let example = b"\n        ..@\r\n        @.@\n\n        ";
+        assert_eq!(*normalize(example, Mode::TEXT), *b"..@\n@.@");
+        assert_eq!(*normalize(example, Mode::COLUMNS), *b"..@\n@.@");
+
+        let ragged = b"  1 2\n    3\n  *\n";
+        assert_eq!(*normalize(ragged, Mode::COLUMNS), *b"1 2\n  3\n*  ");
+
+        let none = Mode {
+            dedent: false,
+            crlf: false,
+            trim_end: false,
+            pad: false,
+        };
+        assert!(matches!(normalize(example, none), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn tgrid() {
+        let cell = |b| match b {
+            b'.' => Some(false),
+            b'#' => Some(true),
+            _ => None,
+        };
+        let input = normalize(b"\n  .#.\n  ##.\n  ", Mode::TEXT);
+        let m = grid(Span::new(&input), cell).unwrap();
+        assert_eq!((m.width, m.height), (3, 2));
+        assert_eq!(m.cells, [false, true, false, true, true, false]);
+
+        // spaces are cells too
+        let m = grid(Span::new(b" #\n# "), |b| Some(b == b' ')).unwrap();
+        assert_eq!(m.cells, [true, false, false, true]);
+
+        let e = grid(Span::new(b".#.\n#x."), cell).unwrap_err();
+        assert_eq!((e.line, e.col), (2, 2));
+        let e = grid(Span::new(b".#.\n#."), cell).unwrap_err();
+        assert_eq!(e.msg, "row is 2 wide, expected 3");
+    }
+}
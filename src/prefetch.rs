@@ -0,0 +1,107 @@
+use std::{path::Path, sync::Arc};
+
+use anyhow::Context;
+use reqwest::{Client, header::COOKIE};
+use tokio::{runtime, task::JoinSet, time::sleep};
+
+use crate::{
+    cache::{compress_enabled, is_cached, write_cached},
+    config::session,
+    fetch::{
+        DAYS, LOCK_POLL, LockGuard, USER_AGENT, YEAR, ensure_cache_dir, ensure_online, input_path,
+        input_url, lock_path, reserve_slot,
+    },
+    manifest::record_fetch,
+    session::check_response,
+    unlock::is_unlocked,
+};
+
+#[derive(Debug)]
+pub enum Prefetched {
+    Fetched,
+    Cached,
+    Locked,
+    Failed(anyhow::Error),
+}
+
+/// Fetch every unlocked, uncached day concurrently, sharing the usual rate limit.
+pub fn prefetch() -> Result<Vec<(u8, Prefetched)>, anyhow::Error> {
+    let rt = runtime::Builder::new_multi_thread().enable_all().build()?;
+    rt.block_on(prefetch_all())
+}
+
+async fn prefetch_all() -> Result<Vec<(u8, Prefetched)>, anyhow::Error> {
+    ensure_cache_dir()?;
+    let client = Client::builder().user_agent(USER_AGENT).build()?;
+    let session: Option<Arc<str>> = session().ok().map(Arc::from);
+
+    let mut tasks = JoinSet::new();
+    for day in 1..=DAYS {
+        let client = client.clone();
+        let session = session.clone();
+        tasks.spawn(async move { (day, prefetch_day(day, client, session).await) });
+    }
+
+    let mut out = tasks.join_all().await;
+    out.sort_by_key(|(day, _)| *day);
+
+    Ok(out)
+}
+
+async fn prefetch_day(day: u8, client: Client, session: Option<Arc<str>>) -> Prefetched {
+    let path = input_path(day);
+    if is_cached(&path) {
+        return Prefetched::Cached;
+    }
+    if !is_unlocked(YEAR, day) {
+        return Prefetched::Locked;
+    }
+
+    match fetch_day(day, &path, &client, session).await {
+        Ok(true) => Prefetched::Fetched,
+        Ok(false) => Prefetched::Cached,
+        Err(e) => Prefetched::Failed(e),
+    }
+}
+
+/// Async twin of `fetch::cached_or_fetch`; `Ok(false)` means another process
+/// filled the cache while we waited on the lock.
+async fn fetch_day(
+    day: u8,
+    path: &Path,
+    client: &Client,
+    session: Option<Arc<str>>,
+) -> Result<bool, anyhow::Error> {
+    let _guard = loop {
+        if let Some(guard) = LockGuard::try_acquire(&lock_path(day))? {
+            break guard;
+        }
+        sleep(LOCK_POLL).await;
+        if is_cached(path) {
+            return Ok(false);
+        }
+    };
+
+    if is_cached(path) {
+        return Ok(false);
+    }
+
+    let session = session.context("SESSION env var is required to fetch input")?;
+    let url = input_url(day);
+    ensure_online(&url)?;
+    sleep(reserve_slot()?).await;
+    let resp = client
+        .get(&url)
+        .header(COOKIE, format!("session={session}"))
+        .send()
+        .await?;
+    let status = resp.status();
+    let text = check_response(&url, status, resp.text().await?)?;
+    write_cached(path, &text, compress_enabled())?;
+    // the input is cached either way, so this isn't worth failing the day for
+    if let Err(e) = record_fetch(day, &text) {
+        eprintln!("warning: couldn't record day {day} in the manifest: {e:#}");
+    }
+
+    Ok(true)
+}
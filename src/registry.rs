@@ -0,0 +1,59 @@
+use crate::*;
+
+/// A day's solver with the answer type erased, for runners that pick days at runtime.
+pub struct Solver {
+    pub day: u8,
+    /// How many parts are really implemented, see `Day::PARTS`.
+    pub parts: u8,
+    pub part1: fn(&str, Arith, Budget) -> String,
+    pub part2: fn(&str, Arith, Budget) -> String,
+    /// Known answers for the default profile's input, see `Day::ANSWERS`.
+    pub expected: [&'static str; 2],
+    /// Which parts honour `Arith`, see `Day::ARITH`.
+    pub arith: [bool; 2],
+}
+
+#[cfg(any_day)]
+pub(crate) fn solver<D: Day>(day: u8) -> Solver {
+    Solver {
+        day,
+        parts: D::PARTS,
+        part1: D::part1_as,
+        part2: D::part2_as,
+        expected: D::ANSWERS,
+        arith: D::ARITH,
+    }
+}
+
+/// Every `src/dayNN.rs`, via `days!` in `lib.rs`.
+pub fn solvers() -> Vec<Solver> {
+    crate::registered()
+}
+
+pub fn solver_for(day: u8) -> Option<Solver> {
+    solvers().into_iter().find(|s| s.day == day)
+}
+
+#[cfg(all(test, feature = "all-days"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tregistry() {
+        let days: Vec<u8> = solvers().iter().map(|s| s.day).collect();
+        assert_eq!(days, (1..=10).collect::<Vec<_>>());
+
+        let d1 = solver_for(1).unwrap();
+        assert_eq!(
+            (d1.part2)(
+                "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82",
+                Arith::Native,
+                Budget::new()
+            ),
+            "6"
+        );
+        assert_eq!(solver_for(10).unwrap().parts, 1);
+        assert!(solver_for(11).is_none());
+        assert_eq!(solver_for(2).unwrap().expected[0], "12850231731");
+    }
+}
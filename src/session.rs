@@ -0,0 +1,168 @@
+use std::fmt::Display;
+
+#[cfg(feature = "fetch")]
+use anyhow::bail;
+#[cfg(feature = "fetch")]
+use reqwest::StatusCode;
+
+use crate::{
+    config::session,
+    fetch::{YEAR, base_url, get_with},
+};
+
+/// AoC answered as if we weren't logged in, i.e. the session cookie is stale.
+#[derive(Debug)]
+pub struct LoggedOut {
+    pub url: String,
+}
+
+impl Display for LoggedOut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} answered with a logged-out page: SESSION is invalid or has expired",
+            self.url
+        )
+    }
+}
+
+impl std::error::Error for LoggedOut {}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SessionStatus {
+    Valid { user: String },
+    Expired,
+    Missing,
+}
+
+/// Every fetch goes through here, so an expired cookie is reported as such
+/// instead of ending up cached as an input.
+#[cfg(feature = "fetch")]
+pub(crate) fn check_response(
+    url: &str,
+    status: StatusCode,
+    body: String,
+) -> Result<String, anyhow::Error> {
+    if is_logged_out(&body) {
+        return Err(LoggedOut {
+            url: url.to_string(),
+        }
+        .into());
+    }
+    if !status.is_success() {
+        bail!("{url} returned {status}");
+    }
+
+    Ok(body)
+}
+
+#[cfg(feature = "fetch")]
+fn is_logged_out(body: &str) -> bool {
+    // the input endpoint says "Please log in"; html pages grow a login link
+    body.contains("Please log in") || body.contains("[Log In]")
+}
+
+/// Ask for the (small) event calendar page and see who AoC thinks we are.
+pub fn check_session() -> Result<SessionStatus, anyhow::Error> {
+    let Ok(session) = session() else {
+        return Ok(SessionStatus::Missing);
+    };
+
+    check_session_at(&base_url(), &session)
+}
+
+fn check_session_at(base: &str, session: &str) -> Result<SessionStatus, anyhow::Error> {
+    match get_with(&format!("{base}/{YEAR}"), session) {
+        Ok(body) => Ok(SessionStatus::Valid {
+            user: user_name(&body).unwrap_or("(unknown)").to_string(),
+        }),
+        Err(e) if e.is::<LoggedOut>() => Ok(SessionStatus::Expired),
+        Err(e) => Err(e),
+    }
+}
+
+fn user_name(page: &str) -> Option<&str> {
+    let (_, rest) = page.split_once(r#"<div class="user">"#)?;
+    let (name, _) = rest.split_once('<')?;
+
+    Some(name.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "fetch")]
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::*;
+
+    const LOGGED_IN: &str = r#"<header><div><h1 class="title-global"><a href="/">Advent of Code</a></h1><div class="user">mattdw <span class="star-count">19*</span></div></div></header>"#;
+    const LOGGED_OUT: &str = r#"<header><div><h1 class="title-global"><a href="/">Advent of Code</a></h1><nav><ul><li><a href="/2025/auth/login">[Log In]</a></li></ul></nav></div></header>"#;
+
+    /// Answer a single HTTP request with `body` and return the base url.
+    #[cfg(feature = "fetch")]
+    fn serve_once(status: &'static str, body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0; 4096];
+            let _ = stream.read(&mut buf);
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        });
+
+        format!("http://{addr}")
+    }
+
+    #[test]
+    fn tuser_name() {
+        assert_eq!(user_name(LOGGED_IN), Some("mattdw"));
+        assert_eq!(user_name(LOGGED_OUT), None);
+    }
+
+    #[cfg(feature = "fetch")]
+    #[test]
+    fn tcheck_response() {
+        let ok = check_response("u", StatusCode::OK, "1,2,3\n".to_string());
+        assert_eq!(ok.unwrap(), "1,2,3\n");
+
+        let input = "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+        let e = check_response("u", StatusCode::BAD_REQUEST, input.to_string()).unwrap_err();
+        assert!(e.is::<LoggedOut>());
+
+        let e = check_response("u", StatusCode::NOT_FOUND, "404".to_string()).unwrap_err();
+        assert!(!e.is::<LoggedOut>());
+    }
+
+    #[cfg(feature = "fetch")]
+    #[test]
+    fn tcheck_session() {
+        if crate::offline() {
+            // not even a local listener gets a request then
+            let e = check_session_at("http://127.0.0.1:9", "abc").unwrap_err();
+            assert!(e.to_string().starts_with("offline, so not requesting"));
+            return;
+        }
+
+        let base = serve_once("200 OK", LOGGED_IN);
+        assert_eq!(
+            check_session_at(&base, "abc").unwrap(),
+            SessionStatus::Valid {
+                user: "mattdw".to_string()
+            }
+        );
+
+        let base = serve_once("200 OK", LOGGED_OUT);
+        assert_eq!(
+            check_session_at(&base, "abc").unwrap(),
+            SessionStatus::Expired
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use std::{
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// AoC unlocks at midnight US Eastern, which it treats as a fixed UTC-5.
+const UNLOCK_HOUR_UTC: u64 = 5;
+
+/// The instant `day` of December `year` becomes available.
+pub fn unlock_time(year: u16, day: u8) -> SystemTime {
+    let days = days_from_civil(year as i64, 12, day as i64) as u64;
+    UNIX_EPOCH + Duration::from_secs(days * 86400 + UNLOCK_HOUR_UTC * 3600)
+}
+
+pub fn is_unlocked(year: u16, day: u8) -> bool {
+    SystemTime::now() >= unlock_time(year, day)
+}
+
+/// Block until `day` unlocks, calling `tick` with the time left about once a second.
+pub fn wait_for_unlock(year: u16, day: u8, mut tick: impl FnMut(Duration)) {
+    while let Ok(remaining) = unlock_time(year, day).duration_since(SystemTime::now()) {
+        tick(remaining);
+        thread::sleep(remaining.min(Duration::from_secs(1)));
+    }
+}
+
+pub fn format_countdown(d: Duration) -> String {
+    let s = d.as_secs();
+    let (days, h, m, s) = (s / 86400, s / 3600 % 24, s / 60 % 60, s % 60);
+    if days > 0 {
+        format!("{days}d {h:02}:{m:02}:{s:02}")
+    } else {
+        format!("{h:02}:{m:02}:{s:02}")
+    }
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date (Howard Hinnant's algorithm).
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (m + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146097 + doe - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(t: SystemTime) -> u64 {
+        t.duration_since(UNIX_EPOCH).unwrap().as_secs()
+    }
+
+    #[test]
+    fn tcivil() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+    }
+
+    #[test]
+    fn tunlock() {
+        assert_eq!(secs(unlock_time(2025, 1)), 1764565200);
+        assert_eq!(secs(unlock_time(2025, 12)), 1765515600);
+        assert_eq!(secs(unlock_time(2024, 25)), 1735102800);
+    }
+
+    #[test]
+    fn tcountdown() {
+        assert_eq!(format_countdown(Duration::from_secs(59)), "00:00:59");
+        assert_eq!(format_countdown(Duration::from_secs(3661)), "01:01:01");
+        assert_eq!(
+            format_countdown(Duration::from_secs(2 * 86400 + 5)),
+            "2d 00:00:05"
+        );
+    }
+
+    #[test]
+    fn twait_unlocked() {
+        let mut ticks = 0;
+        wait_for_unlock(2015, 1, |_| ticks += 1);
+        assert_eq!(ticks, 0);
+    }
+
+    #[test]
+    fn tunlocked() {
+        assert!(is_unlocked(2015, 1));
+        assert!(!is_unlocked(9999, 1));
+    }
+}
@@ -0,0 +1,36 @@
+//! Small helpers standing in for std APIs that are still nightly-only, so the
+//! crate builds on stable.
+
+// which of these are used depends on the days built
+#![cfg_attr(not(feature = "all-days"), allow(dead_code, unused_imports))]
+
+use std::ops::RangeInclusive;
+
+/// `[min, max]` of two values, like `std::cmp::minmax`.
+pub(crate) fn minmax<T: Ord>(a: T, b: T) -> [T; 2] {
+    if b < a { [b, a] } else { [a, b] }
+}
+
+/// Do two inclusive ranges share a value? Like `GetDisjointMutIndex::is_overlapping`,
+/// but for any ordered type.
+pub(crate) fn overlaps<T: Ord>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> bool {
+    a.start() <= b.end() && b.start() <= a.end()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tminmax() {
+        assert_eq!(minmax(3, -1), [-1, 3]);
+        assert_eq!(minmax(2, 2), [2, 2]);
+    }
+
+    #[test]
+    fn toverlaps() {
+        assert!(overlaps(&(3..=5), &(5..=7)));
+        assert!(overlaps(&(1..=10), &(4..=4)));
+        assert!(!overlaps(&(3..=5), &(6..=7)));
+    }
+}
@@ -1,35 +1,40 @@
+use aoc25_rs::for_each_day;
+
+// unused in a build with no days
+#[allow(unused_macros)]
 macro_rules! check {
-    ($name:ident, $struct:ty, $p1:literal, $p2:literal) => {
+    ($name:ident, $struct:ty) => {
         mod $name {
 
             use aoc25_rs::*;
 
             #[test]
             fn part1() {
-                let input = fetch_input_s(stringify!($struct)).unwrap();
+                let day = day_number(stringify!($struct));
+                let expected =
+                    expected_answer(day, 1, <$struct>::ANSWERS[0]).unwrap_or_else(|| {
+                        panic!("no expected answer for day {day} part 1 in this profile")
+                    });
+                let input = fetch_input(day).unwrap();
                 let res = <$struct>::part1(&input);
-                assert_eq!($p1.to_string(), res.to_string());
+                assert_eq!(expected, res.to_string());
+                record_verified(day, 1, &res.to_string()).unwrap();
             }
 
             #[test]
             fn part2() {
-                let input = fetch_input_s(stringify!($struct)).unwrap();
+                let day = day_number(stringify!($struct));
+                let expected =
+                    expected_answer(day, 2, <$struct>::ANSWERS[1]).unwrap_or_else(|| {
+                        panic!("no expected answer for day {day} part 2 in this profile")
+                    });
+                let input = fetch_input(day).unwrap();
                 let res = <$struct>::part2(&input);
-                assert_eq!($p2.to_string(), res.to_string());
+                assert_eq!(expected, res.to_string());
+                record_verified(day, 2, &res.to_string()).unwrap();
             }
         }
     };
 }
 
-check!(day01, Day1, 1177, 6768);
-check!(day02, Day2, 12850231731u64, 24774350322u64);
-check!(day03, Day3, 17554, 175053592950232u64);
-check!(day04, Day4, 1428, 8936);
-check!(day05, Day5, 558, 344813017450467u64);
-check!(day06, Day6, 4805473544166u64, 8907730960817u64);
-check!(day07, Day7, 1543, 3223365367809u64);
-check!(day08, Day8, 26400, 8199963486u64);
-check!(day09, Day9, 4777409595u64, 1473551379u64);
-check!(day10, Day10, 417, "👻");
-// check!(day11, Day11, "👻", "👻");
-// check!(day12, Day12, "👻", "👻");
+for_each_day!(check);